@@ -1,9 +1,11 @@
+use alloc::collections::TryReserveError;
 use alloc::string::String;
 use core::fmt::Write;
 use core::ops::Deref;
 use core::{fmt, mem, ptr, str};
 
 use crate::storage::inline::STRING_SIZED_INLINE;
+use crate::FlexStr;
 
 // The size of internal buffer for formatting (if larger needed we punt and just use a heap allocated String)
 #[doc(hidden)]
@@ -191,6 +193,171 @@ impl<const N: usize, const N2: usize> ufmt_write::uWrite for FlexStrBuilder<'_,
     }
 }
 
+// *** Reusable Builder ***
+
+/// A string builder that can be reused to build many strings. Its heap buffer is kept between
+/// strings so that building in a loop only allocates until the buffer has grown large enough.
+/// Strings that are short enough are inlined when finished, otherwise they are copied into a new
+/// heap allocation.
+/// ```
+/// use flexstr::{LocalStr, ReusableBuilder};
+///
+/// let mut builder = ReusableBuilder::new();
+///
+/// for i in 0..3 {
+///     builder.push_str("item");
+///     builder.push(char::from(b'0' + i));
+///     let s: LocalStr = builder.finish();
+///     assert!(s.is_inline());
+///     assert_eq!(s, format!("item{i}"));
+///     assert!(builder.is_empty());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReusableBuilder {
+    buffer: String,
+}
+
+impl ReusableBuilder {
+    /// Creates a new empty builder. This does not allocate.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            buffer: String::new(),
+        }
+    }
+
+    /// Creates a new empty builder with a buffer of at least `cap` bytes
+    #[inline]
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            buffer: String::with_capacity(cap),
+        }
+    }
+
+    /// Returns the contents written since the builder was last finished or cleared
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Returns the length of the current contents in bytes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns true if nothing has been written since the builder was last finished or cleared
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns the capacity of the reused buffer in bytes
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Appends `s` to the current contents
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.buffer.push_str(s);
+    }
+
+    /// Appends `s` to the current contents, returning an error instead of aborting if the buffer
+    /// needs to grow and the allocation fails. The contents are unchanged on error.
+    /// ```
+    /// use flexstr::ReusableBuilder;
+    ///
+    /// let mut builder = ReusableBuilder::new();
+    /// builder.try_push_str("test").unwrap();
+    /// assert_eq!(builder.as_str(), "test");
+    /// ```
+    #[inline]
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), TryReserveError> {
+        self.buffer.try_reserve(s.len())?;
+        self.buffer.push_str(s);
+        Ok(())
+    }
+
+    /// Tries to reserve capacity for at least `additional` more bytes, returning an error instead of
+    /// aborting if the allocation fails
+    /// ```
+    /// use flexstr::ReusableBuilder;
+    ///
+    /// let mut builder = ReusableBuilder::new();
+    /// builder.try_reserve(64).unwrap();
+    /// assert!(builder.capacity() >= 64);
+    /// assert!(builder.try_reserve(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buffer.try_reserve(additional)
+    }
+
+    /// Appends the char `c` to the current contents
+    #[inline]
+    pub fn push(&mut self, c: char) {
+        self.buffer.push(c);
+    }
+
+    /// Discards the current contents, keeping the buffer for reuse
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns a new [FlexStr] with the current contents and clears the builder for reuse. The
+    /// result is inlined when possible.
+    #[inline]
+    pub fn finish<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>(
+        &mut self,
+    ) -> FlexStr<SIZE, PAD1, PAD2, HEAP>
+    where
+        HEAP: for<'a> From<&'a str>,
+    {
+        let s = FlexStr::from_ref(&self.buffer);
+        self.buffer.clear();
+        s
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    FlexStr<SIZE, PAD1, PAD2, HEAP>
+{
+    /// Returns a [ReusableBuilder] with a heap buffer of at least `cap` bytes. When a string is
+    /// known to be large, writing to this skips the stack buffer used by formatting and its
+    /// eventual promotion to the heap, so no writes up to `cap` bytes allocate again. Note that
+    /// [finish](ReusableBuilder::finish) still copies the contents into a new heap string (a second
+    /// allocation), leaving the buffer free for reuse.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let mut builder = LocalStr::heap_with_capacity(1024);
+    /// for _ in 0..32 {
+    ///     builder.push_str("This is a large string!!");
+    /// }
+    /// assert!(builder.capacity() >= 1024);
+    ///
+    /// let s: LocalStr = builder.finish();
+    /// assert!(s.is_heap());
+    /// assert_eq!(s.len(), 768);
+    /// ```
+    #[inline]
+    pub fn heap_with_capacity(cap: usize) -> ReusableBuilder {
+        ReusableBuilder::with_capacity(cap)
+    }
+}
+
+impl Write for ReusableBuilder {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
 // NOTE: This is a macro as the inliner was copying our stack buffer
 #[doc(hidden)]
 #[macro_export]
@@ -284,4 +451,105 @@ mod tests {
         let s: LocalStr = builder_into!(builder, buffer);
         assert_eq!(s, write.to_string() + &write2);
     }
+
+    #[test]
+    fn reusable_builder_try_reserve_failure() {
+        use crate::ReusableBuilder;
+
+        let mut builder = ReusableBuilder::new();
+        builder.try_push_str("test").unwrap();
+
+        // Growing past `isize::MAX` always fails - the builder must be left untouched and usable
+        assert!(builder.try_reserve(usize::MAX - 1).is_err());
+        assert_eq!(builder.as_str(), "test");
+        builder.try_push_str("ing").unwrap();
+        assert_eq!(builder.as_str(), "testing");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reusable_builder_try_push_str_allocation_failure() {
+        use crate::counting_alloc::with_failing_allocations;
+        use crate::ReusableBuilder;
+
+        let mut builder = ReusableBuilder::new();
+        builder.try_push_str("test").unwrap();
+        let cap = builder.capacity();
+        let more = "x".repeat(cap + 1);
+
+        // The reservation is a valid size, so this fails in the allocator itself
+        let result = with_failing_allocations(|| builder.try_push_str(&more));
+        let err = result.unwrap_err();
+        assert!(format!("{:?}", err).contains("AllocError"), "{:?}", err);
+        assert_eq!(builder.as_str(), "test");
+        assert_eq!(builder.capacity(), cap);
+
+        // Once allocation succeeds again the builder is still usable
+        builder.try_push_str(&more).unwrap();
+        assert_eq!(builder.as_str(), "test".to_string() + &more);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reusable_builder_amortizes_allocations() {
+        use crate::counting_alloc::count_allocations;
+        use crate::ReusableBuilder;
+
+        let mut builder = ReusableBuilder::new();
+        // Warm up the buffer
+        builder.push_str(&"x".repeat(64));
+        builder.clear();
+
+        let allocations = count_allocations(|| {
+            for i in 0..1000 {
+                write!(builder, "iteration-{}", i).unwrap();
+                let s: LocalStr = builder.finish();
+                assert!(s.is_inline());
+            }
+        });
+        assert_eq!(allocations, 0);
+
+        // Heap results only allocate the string itself
+        let allocations = count_allocations(|| {
+            builder.push_str("This is too long to be inlined!!!");
+            let s: LocalStr = builder.finish();
+            assert!(s.is_heap());
+        });
+        assert_eq!(allocations, 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn heap_with_capacity_allocations() {
+        use crate::counting_alloc::count_allocations;
+
+        let part = "This is a large string!!";
+        let mut builder = None;
+        let allocations = count_allocations(|| {
+            let mut b = LocalStr::heap_with_capacity(part.len() * 100);
+            for _ in 0..100 {
+                b.push_str(part);
+            }
+            assert_eq!(b.len(), part.len() * 100);
+
+            // The buffer is allocated once, and `finish` copies the contents into a new `Rc`
+            let s: LocalStr = b.finish();
+            assert!(s.is_heap());
+            assert_eq!(s.len(), part.len() * 100);
+            builder = Some(b);
+        });
+        assert_eq!(allocations, 2);
+
+        // Reusing the builder only allocates the resulting string
+        let mut builder = builder.unwrap();
+        let allocations = count_allocations(|| {
+            builder.push_str(&part[..1]);
+            for _ in 0..99 {
+                builder.push_str(part);
+            }
+            let s: LocalStr = builder.finish();
+            assert!(s.is_heap());
+        });
+        assert_eq!(allocations, 1);
+    }
 }
@@ -0,0 +1,75 @@
+use core::ops::Deref;
+
+use compact_str::CompactString;
+
+use crate::FlexStr;
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> From<CompactString>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: for<'a> From<&'a str>,
+{
+    /// Converts a [CompactString] preserving its storage where possible: an inlined
+    /// [CompactString] is inlined (if it fits, as its inline capacity is slightly larger) and a heap
+    /// allocated one is copied to the heap.
+    /// ```
+    /// use compact_str::CompactString;
+    /// use flexstr::LocalStr;
+    ///
+    /// let s: LocalStr = CompactString::new("inline").into();
+    /// assert!(s.is_inline());
+    ///
+    /// let s: LocalStr = CompactString::new("This is too long to be inlined!!!").into();
+    /// assert!(s.is_heap());
+    /// assert_eq!(s, "This is too long to be inlined!!!");
+    /// ```
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        if s.is_heap_allocated() {
+            Self::from_ref_heap(s.as_str())
+        } else {
+            Self::from_ref(s.as_str())
+        }
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    From<FlexStr<SIZE, PAD1, PAD2, HEAP>> for CompactString
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Converts to a [CompactString], which inlines the contents if they fit, otherwise they are
+    /// copied to the heap
+    /// ```
+    /// use compact_str::CompactString;
+    /// use flexstr::local_str;
+    ///
+    /// let s: CompactString = local_str!("inline").into();
+    /// assert!(!s.is_heap_allocated());
+    /// assert_eq!(s, "inline");
+    /// ```
+    #[inline]
+    fn from(s: FlexStr<SIZE, PAD1, PAD2, HEAP>) -> Self {
+        CompactString::new(s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    #[test]
+    fn short_compact_string_does_not_allocate() {
+        use crate::counting_alloc::count_allocations;
+        use crate::LocalStr;
+        use compact_str::CompactString;
+
+        let compact = CompactString::new("inline");
+        let allocations = count_allocations(|| {
+            let s: LocalStr = compact.into();
+            assert!(s.is_inline());
+            let back: CompactString = s.into();
+            assert_eq!(back, "inline");
+        });
+        assert_eq!(allocations, 0);
+    }
+}
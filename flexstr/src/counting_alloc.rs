@@ -0,0 +1,65 @@
+// Test-only global allocator that counts heap allocations made by the current thread and can be
+// made to fail them. Both are per thread so tests running in parallel don't interfere with each
+// other.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ptr;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static FAIL_ALLOCATIONS: Cell<bool> = const { Cell::new(false) };
+}
+
+// Returns true if allocations on this thread should currently fail
+#[inline]
+fn should_fail() -> bool {
+    FAIL_ALLOCATIONS.try_with(Cell::get).unwrap_or(false)
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if should_fail() {
+            return ptr::null_mut();
+        }
+        // Ignore failures: the thread local may already be destroyed during thread shutdown
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if should_fail() {
+            return ptr::null_mut();
+        }
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// Returns the number of allocations (and reallocations) made on this thread while running `f`
+pub(crate) fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+// Runs `f` with every allocation (and reallocation) on this thread failing. `f` must not panic, as
+// unwinding needs to allocate.
+pub(crate) fn with_failing_allocations<R>(f: impl FnOnce() -> R) -> R {
+    FAIL_ALLOCATIONS.with(|fail| fail.set(true));
+    let result = f();
+    FAIL_ALLOCATIONS.with(|fail| fail.set(false));
+    result
+}
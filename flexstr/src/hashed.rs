@@ -0,0 +1,206 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::SharedStr;
+
+// Multiplier used by the Fx hash algorithm (from rustc)
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+// *** FxHasher ***
+
+// A simple, fast, and deterministic (non-randomized) hasher based on the Fx hash used by rustc. It is
+// NOT resistant to hash flooding, but it always returns the same value for the same input
+#[derive(Default)]
+pub(crate) struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut word = [0; 8];
+            word[..remainder.len()].copy_from_slice(remainder);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+// Hashes `value` with an [FxHasher]
+#[inline]
+pub(crate) fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = FxHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// *** HashedStr ***
+
+/// A [SharedStr] paired with a precomputed hash of its contents. When hashed, only the cached value
+/// is written to the [Hasher] so the string contents never need to be rehashed. This is useful for
+/// long strings that are frequently used as keys in a `HashMap` or `HashSet`.
+///
+/// # Note
+/// The cached value is calculated with a fixed (non-randomized) hash algorithm so that it is the
+/// same for the same contents regardless of where it was computed. It is therefore not resistant to
+/// hash flooding attacks and should not be used for keys supplied by untrusted sources.
+/// ```
+/// use std::collections::HashMap;
+/// use flexstr::{shared_str, HashedStr};
+///
+/// let key = HashedStr::new(shared_str!("This is a long key that would be expensive to rehash"));
+/// assert_eq!(key.cached_hash(), HashedStr::hash_str(&key));
+///
+/// let mut map = HashMap::new();
+/// map.insert(key.clone(), 1);
+/// assert_eq!(map.get(&key), Some(&1));
+/// ```
+#[derive(Clone)]
+pub struct HashedStr {
+    s: SharedStr,
+    hash: u64,
+}
+
+impl HashedStr {
+    /// Creates a new [HashedStr] calculating and storing the hash of the string contents
+    #[inline]
+    pub fn new(s: SharedStr) -> Self {
+        let hash = Self::hash_str(&s);
+        Self { s, hash }
+    }
+
+    /// Calculates the hash of `s` using the same algorithm used by [HashedStr] to cache its hash
+    #[inline]
+    pub fn hash_str(s: &str) -> u64 {
+        hash_of(s)
+    }
+
+    /// Returns the precomputed hash of the string contents
+    #[inline]
+    pub fn cached_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns a reference to the wrapped [SharedStr]
+    #[inline]
+    pub fn as_shared_str(&self) -> &SharedStr {
+        &self.s
+    }
+
+    /// Consumes this value returning the wrapped [SharedStr]
+    #[inline]
+    pub fn into_shared_str(self) -> SharedStr {
+        self.s
+    }
+}
+
+impl Deref for HashedStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.s
+    }
+}
+
+impl AsRef<str> for HashedStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.s
+    }
+}
+
+impl Hash for HashedStr {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash)
+    }
+}
+
+impl PartialEq for HashedStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Differing hashes means the contents must differ, so we can skip the string comparison
+        self.hash == other.hash && self.s == other.s
+    }
+}
+
+impl Eq for HashedStr {}
+
+impl Debug for HashedStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <SharedStr as Debug>::fmt(&self.s, f)
+    }
+}
+
+impl Display for HashedStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        <SharedStr as Display>::fmt(&self.s, f)
+    }
+}
+
+impl From<SharedStr> for HashedStr {
+    #[inline]
+    fn from(s: SharedStr) -> Self {
+        Self::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shared_str, HashedStr, SharedStr};
+
+    #[test]
+    fn cached_hash_matches_content_hash() {
+        let lit = "This is a string long enough to be heap allocated";
+        let s: SharedStr = lit.into();
+        assert!(s.is_heap());
+
+        let hashed = HashedStr::new(s);
+        assert_eq!(hashed.cached_hash(), HashedStr::hash_str(lit));
+        // Storage kind must not influence the hash
+        assert_eq!(
+            hashed.cached_hash(),
+            HashedStr::new(SharedStr::from_static(lit)).cached_hash()
+        );
+    }
+
+    #[test]
+    fn differing_contents() {
+        let a = HashedStr::new(shared_str!("a"));
+        let b = HashedStr::new(shared_str!("b"));
+        assert_ne!(a.cached_hash(), b.cached_hash());
+        assert_ne!(a, b);
+        assert_eq!(a, HashedStr::new("a".into()));
+    }
+}
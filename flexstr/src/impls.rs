@@ -1,4 +1,7 @@
-use alloc::string::String;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{FromUtf8Error, String};
+use alloc::sync::Arc;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::convert::Infallible;
@@ -10,26 +13,47 @@ use core::marker::PhantomData;
 use core::ops::{
     Add, Deref, Index, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
 };
-use core::str::FromStr;
+use core::str::{FromStr, Utf8Error};
 
 #[cfg(feature = "serde")]
 use serde::de::{Error, Visitor};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{from_iter_char, from_iter_str, FlexStr, IntoFlex};
+use crate::ops::CharsIntoIter;
+use crate::storage::inline::InlineFlexStr;
+use crate::{
+    from_iter_char, from_iter_flex, from_iter_str, BoxedStr, FlexStr, HeapStorage, IntoFlex,
+    LocalStr, SharedStr,
+};
 
 // *** Debug / Display ***
 
-// FIXME: Do we want to do something custom?
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Debug
     for FlexStr<SIZE, PAD1, PAD2, HEAP>
 where
     HEAP: Deref<Target = str>,
 {
+    /// Formats the same as [str], unless the alternate flag is used in which case the storage
+    /// type is also shown
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// assert_eq!(format!("{:?}", local_str!("test")), r#""test""#);
+    /// assert_eq!(format!("{:#?}", local_str!("test")), r#"Static("test")"#);
+    /// assert_eq!(format!("{:#?}", LocalStr::from_ref("test")), r#"Inline("test")"#);
+    /// assert_eq!(format!("{:#?}", LocalStr::from_ref_heap("test")), r#"Heap("test")"#);
+    /// ```
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        <str as Debug>::fmt(self, f)
+        if f.alternate() {
+            Debug::fmt(&self.storage_type(), f)?;
+            f.write_str("(")?;
+            <str as Debug>::fmt(self, f)?;
+            f.write_str(")")
+        } else {
+            <str as Debug>::fmt(self, f)
+        }
     }
 }
 
@@ -38,6 +62,23 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Display
 where
     HEAP: Deref<Target = str>,
 {
+    /// Formats the string contents in place (without copying) so width, fill, alignment and
+    /// precision behave exactly as for [str]. Precision truncates to a number of chars (not bytes).
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// for s in [
+    ///     local_str!("a☺ü"),
+    ///     LocalStr::from_ref("a☺ü"),
+    ///     LocalStr::from_ref_heap("a☺ü"),
+    /// ] {
+    ///     assert_eq!(format!("{:.2}", s), "a☺");
+    ///     assert_eq!(format!("{:>5}", s), "  a☺ü");
+    ///     assert_eq!(format!("{:-<5}", s), "a☺ü--");
+    ///     assert_eq!(format!("{:*^7.2}", s), "**a☺***");
+    ///     assert_eq!(format!("{:1}", s), "a☺ü");
+    /// }
+    /// ```
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         <str as Display>::fmt(self, f)
@@ -82,6 +123,9 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Hash
 where
     HEAP: Deref<Target = str>,
 {
+    /// Only the string contents are hashed (never the storage marker or padding bytes), so equal
+    /// strings hash identically regardless of storage type or `HEAP` type, and identically to the
+    /// equivalent [str] as required by the [Borrow] implementation.
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
         str::hash(self, state)
@@ -182,6 +226,84 @@ where
     }
 }
 
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> PartialEq<char>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Returns true if this string consists of exactly the char `other`
+    /// ```
+    /// use flexstr::local_str;
+    ///
+    /// assert_eq!(local_str!("😀"), '😀');
+    /// assert_ne!(local_str!("ab"), 'a');
+    /// assert_ne!(local_str!(""), 'a');
+    /// ```
+    #[inline]
+    fn eq(&self, other: &char) -> bool {
+        let mut chars = self.chars();
+        chars.next() == Some(*other) && chars.next().is_none()
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    PartialEq<FlexStr<SIZE, PAD1, PAD2, HEAP>> for char
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Returns true if `other` consists of exactly this char
+    /// ```
+    /// use flexstr::local_str;
+    ///
+    /// assert_eq!('😀', local_str!("😀"));
+    /// assert_ne!('a', local_str!("ab"));
+    /// ```
+    #[inline]
+    fn eq(&self, other: &FlexStr<SIZE, PAD1, PAD2, HEAP>) -> bool {
+        other == self
+    }
+}
+
+impl<const N: usize, const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    PartialEq<InlineFlexStr<N>> for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    /// use flexstr::storage::inline::InlineFlexStr;
+    ///
+    /// let inline = <InlineFlexStr>::try_new("test").unwrap();
+    /// assert_eq!(local_str!("test"), inline);
+    /// assert_eq!(LocalStr::from_ref("test"), inline);
+    /// assert_ne!(local_str!("other"), inline);
+    /// ```
+    #[inline]
+    fn eq(&self, other: &InlineFlexStr<N>) -> bool {
+        str::eq(self, &**other)
+    }
+}
+
+impl<const N: usize, const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    PartialEq<FlexStr<SIZE, PAD1, PAD2, HEAP>> for InlineFlexStr<N>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    /// use flexstr::storage::inline::InlineFlexStr;
+    ///
+    /// let inline = <InlineFlexStr>::try_new("test").unwrap();
+    /// assert_eq!(inline, local_str!("test"));
+    /// assert_eq!(inline, LocalStr::from_ref("test"));
+    /// assert_ne!(inline, local_str!("other"));
+    /// ```
+    #[inline]
+    fn eq(&self, other: &FlexStr<SIZE, PAD1, PAD2, HEAP>) -> bool {
+        str::eq(self, &**other)
+    }
+}
+
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Eq
     for FlexStr<SIZE, PAD1, PAD2, HEAP>
 where
@@ -198,7 +320,26 @@ where
 {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        str::partial_cmp(self, other)
+        Some(self.cmp(other))
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> PartialOrd<&str>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// ```
+    /// use flexstr::local_str;
+    ///
+    /// let s = local_str!("b");
+    /// assert!(s > "a");
+    /// assert!(s < "c");
+    /// assert!(s >= "b");
+    /// ```
+    #[inline]
+    fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+        str::partial_cmp(self, *other)
     }
 }
 
@@ -295,6 +436,22 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Borrow<str>
 where
     HEAP: Deref<Target = str>,
 {
+    /// Allows maps and sets keyed by [FlexStr] to be queried with a plain `&str`. `Hash`, `Eq` and
+    /// `Ord` all delegate to [str], so they are consistent with the borrowed form.
+    ///
+    /// # Note
+    /// `Borrow<[u8]>` is intentionally not implemented as [str] and `[u8]` hash differently.
+    /// ```
+    /// use std::collections::HashMap;
+    /// use flexstr::{shared_str, SharedStr};
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(shared_str!("key"), 1);
+    /// map.insert(SharedStr::from_ref("This is a heap allocated key!!!"), 2);
+    /// assert_eq!(map.get("key"), Some(&1));
+    /// assert_eq!(map.get("This is a heap allocated key!!!"), Some(&2));
+    /// assert_eq!(map.get("missing"), None);
+    /// ```
     #[inline]
     fn borrow(&self) -> &str {
         str::borrow(self)
@@ -312,6 +469,56 @@ where
     }
 }
 
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> AsRef<[u8]>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Since [FlexStr] has more than one [AsRef] impl, the target type must be known at the call
+    /// site (or use [as_bytes](str::as_bytes) directly).
+    /// ```
+    /// use flexstr::local_str;
+    ///
+    /// let s = local_str!("test");
+    /// let bytes: &[u8] = s.as_ref();
+    /// assert_eq!(bytes, b"test");
+    /// ```
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> AsRef<std::ffi::OsStr>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    #[inline]
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        self.as_os_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> AsRef<std::path::Path>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// ```
+    /// use std::fs;
+    /// use flexstr::local_str;
+    ///
+    /// assert!(fs::metadata(local_str!("/definitely/not/a/real/path")).is_err());
+    /// ```
+    #[inline]
+    fn as_ref(&self) -> &std::path::Path {
+        self.as_path()
+    }
+}
+
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Default
     for FlexStr<SIZE, PAD1, PAD2, HEAP>
 {
@@ -339,8 +546,8 @@ where
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP, HEAP2>
     From<&FlexStr<SIZE, PAD1, PAD2, HEAP2>> for FlexStr<SIZE, PAD1, PAD2, HEAP>
 where
-    HEAP: for<'a> From<&'a str>,
-    HEAP2: Clone + Deref<Target = str>,
+    HEAP: for<'a> From<&'a str> + 'static,
+    HEAP2: Clone + Deref<Target = str> + 'static,
 {
     #[inline]
     fn from(s: &FlexStr<SIZE, PAD1, PAD2, HEAP2>) -> Self {
@@ -415,6 +622,30 @@ where
     }
 }
 
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> TryFrom<&[u8]>
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: for<'a> From<&'a str>,
+{
+    type Error = Utf8Error;
+
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::try_from(&b"inline"[..]).unwrap();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "inline");
+    /// assert!(LocalStr::try_from(&b""[..]).unwrap().is_static());
+    ///
+    /// let err = LocalStr::try_from(&b"in\xFFline"[..]).unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 2);
+    /// ```
+    #[inline]
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_utf8(bytes)
+    }
+}
+
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> From<char>
     for FlexStr<SIZE, PAD1, PAD2, HEAP>
 where
@@ -433,6 +664,199 @@ where
     }
 }
 
+impl From<Rc<str>> for LocalStr {
+    /// Wraps the [`Rc<str>`] as heap storage without copying or allocating (even if short enough to
+    /// be inlined)
+    /// ```
+    /// use std::rc::Rc;
+    /// use flexstr::LocalStr;
+    ///
+    /// let rc: Rc<str> = "test".into();
+    /// let s: LocalStr = rc.clone().into();
+    /// assert!(s.is_heap());
+    /// assert!(Rc::ptr_eq(&s.try_to_heap().unwrap(), &rc));
+    /// ```
+    #[inline]
+    fn from(rc: Rc<str>) -> Self {
+        Self::from_heap(rc)
+    }
+}
+
+impl From<Arc<str>> for SharedStr {
+    /// Wraps the [`Arc<str>`] as heap storage without copying or allocating (even if short enough to
+    /// be inlined)
+    /// ```
+    /// use std::sync::Arc;
+    /// use flexstr::SharedStr;
+    ///
+    /// let arc: Arc<str> = "test".into();
+    /// let s: SharedStr = arc.clone().into();
+    /// assert!(s.is_heap());
+    /// assert!(Arc::ptr_eq(&s.try_to_heap().unwrap(), &arc));
+    /// ```
+    #[inline]
+    fn from(arc: Arc<str>) -> Self {
+        Self::from_heap(arc)
+    }
+}
+
+impl From<Box<str>> for BoxedStr {
+    /// Wraps the [`Box<str>`] as heap storage without copying or allocating (even if short enough to
+    /// be inlined)
+    /// ```
+    /// use flexstr::BoxedStr;
+    ///
+    /// let boxed: Box<str> = "test".into();
+    /// let ptr = boxed.as_ptr();
+    /// let s: BoxedStr = boxed.into();
+    /// assert!(s.is_heap());
+    /// assert_eq!(s.as_ptr(), ptr);
+    /// ```
+    #[inline]
+    fn from(boxed: Box<str>) -> Self {
+        Self::from_heap(boxed)
+    }
+}
+
+impl TryFrom<Box<[u8]>> for BoxedStr {
+    type Error = FromUtf8Error;
+
+    /// Validates the bytes as UTF-8 and moves the [`Box<[u8]>`](Box) into heap storage without
+    /// copying or allocating. On error the original bytes can be recovered from the error.
+    /// ```
+    /// use flexstr::BoxedStr;
+    ///
+    /// let boxed: Box<[u8]> = b"test"[..].into();
+    /// let ptr = boxed.as_ptr();
+    /// let s = BoxedStr::try_from(boxed).unwrap();
+    /// assert!(s.is_heap());
+    /// assert_eq!(s.as_ptr(), ptr);
+    ///
+    /// let err = BoxedStr::try_from(Box::<[u8]>::from(&b"in\xFFvalid"[..])).unwrap_err();
+    /// assert_eq!(err.into_bytes(), b"in\xFFvalid");
+    /// ```
+    #[inline]
+    fn try_from(boxed: Box<[u8]>) -> Result<Self, Self::Error> {
+        // The `Vec` and `String` conversions reuse the allocation and the capacity is exact
+        String::from_utf8(boxed.into_vec()).map(|s| Self::from_heap(s.into_boxed_str()))
+    }
+}
+
+impl TryFrom<LocalStr> for Rc<str> {
+    type Error = LocalStr;
+
+    /// Moves the [`Rc<str>`] out of a heap based [LocalStr] without cloning it. Any other storage
+    /// type returns the original string as the error (use [to_heap](FlexStr::to_heap) to always
+    /// get one).
+    /// ```
+    /// use std::rc::Rc;
+    /// use flexstr::LocalStr;
+    ///
+    /// let rc: Rc<str> = "test".into();
+    /// let moved: Rc<str> = LocalStr::from_heap(rc.clone()).try_into().unwrap();
+    /// assert!(Rc::ptr_eq(&moved, &rc));
+    ///
+    /// let inline = LocalStr::try_inline("test").unwrap();
+    /// assert!(Rc::<str>::try_from(inline.clone()).is_err());
+    /// assert_eq!(inline.to_heap(), "test".into());
+    /// ```
+    #[inline]
+    fn try_from(s: LocalStr) -> Result<Self, Self::Error> {
+        s.try_into_heap()
+    }
+}
+
+impl TryFrom<SharedStr> for Arc<str> {
+    type Error = SharedStr;
+
+    /// Moves the [`Arc<str>`] out of a heap based [SharedStr] without cloning it. Any other storage
+    /// type returns the original string as the error (use [to_heap](FlexStr::to_heap) to always
+    /// get one).
+    /// ```
+    /// use std::sync::Arc;
+    /// use flexstr::SharedStr;
+    ///
+    /// let arc: Arc<str> = "test".into();
+    /// let moved: Arc<str> = SharedStr::from_heap(arc.clone()).try_into().unwrap();
+    /// assert!(Arc::ptr_eq(&moved, &arc));
+    ///
+    /// let inline = SharedStr::try_inline("test").unwrap();
+    /// assert!(Arc::<str>::try_from(inline.clone()).is_err());
+    /// assert_eq!(inline.to_heap(), "test".into());
+    /// ```
+    #[inline]
+    fn try_from(s: SharedStr) -> Result<Self, Self::Error> {
+        s.try_into_heap()
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    From<FlexStr<SIZE, PAD1, PAD2, HEAP>> for String
+where
+    HEAP: HeapStorage,
+{
+    /// Converts a [FlexStr] into a [String]. For heap based strings the heap storage is converted
+    /// via [into_string](HeapStorage::into_string), which moves the allocation of a [BoxedStr]
+    /// without copying. Reference counted heap storage ([LocalStr] and [SharedStr]) cannot give up
+    /// its allocation (it also holds the reference counts), so its contents are always copied.
+    /// ```
+    /// use flexstr::{BoxedStr, SharedStr};
+    ///
+    /// let boxed = BoxedStr::from_ref_heap("This is too long to be inlined!!!");
+    /// let ptr = boxed.as_ptr();
+    /// let s = String::from(boxed);
+    /// assert_eq!(s.as_ptr(), ptr);
+    ///
+    /// let shared = SharedStr::from_ref_heap("This is too long to be inlined!!!");
+    /// let s = String::from(shared.clone());
+    /// assert_ne!(s.as_ptr(), shared.as_ptr());
+    /// assert_eq!(shared, s);
+    ///
+    /// assert_eq!(String::from(SharedStr::from_ref("inline")), "inline");
+    /// ```
+    #[inline]
+    fn from(s: FlexStr<SIZE, PAD1, PAD2, HEAP>) -> Self {
+        match s.try_into_heap() {
+            Ok(heap) => heap.into_string(),
+            Err(s) => s.to_std_string(),
+        }
+    }
+}
+
+// *** IntoIterator ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> IntoIterator
+    for FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    type Item = char;
+    type IntoIter = CharsIntoIter<SIZE, PAD1, PAD2, HEAP>;
+
+    /// Consumes the string returning an iterator over its chars. Any heap storage is released
+    /// when the iterator is dropped.
+    /// ```
+    /// use std::rc::Rc;
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let chars: Vec<char> = local_str!("a☺c").into_iter().rev().collect();
+    /// assert_eq!(chars, vec!['c', '☺', 'a']);
+    /// assert_eq!(local_str!("").into_iter().next(), None);
+    ///
+    /// let rc: Rc<str> = "heap".into();
+    /// let mut iter = LocalStr::from_heap(rc.clone()).into_iter();
+    /// assert_eq!(iter.next(), Some('h'));
+    /// assert_eq!(iter.as_str(), "eap");
+    /// assert_eq!(Rc::strong_count(&rc), 2);
+    /// assert_eq!(iter.count(), 3);
+    /// assert_eq!(Rc::strong_count(&rc), 1);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        CharsIntoIter::new(self)
+    }
+}
+
 // *** FromIterator ***
 
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP, HEAP2>
@@ -442,16 +866,20 @@ where
     HEAP2: Deref<Target = str>,
 {
     /// ```
-    /// use flexstr::LocalStr;
+    /// use flexstr::{local_str, LocalStr};
     ///
     /// let v: Vec<LocalStr> = vec!["best".into(), "test".into()];
     /// let s: LocalStr = v.into_iter().map(|s| if s == "best" { "test".into() } else { s }).collect();
     /// assert!(s.is_inline());
     /// assert_eq!(s, "testtest");
+    ///
+    /// // A single wrapped literal is not copied
+    /// let s: LocalStr = vec![local_str!("literal")].into_iter().collect();
+    /// assert!(s.is_static());
     /// ```
     #[inline]
     fn from_iter<I: IntoIterator<Item = FlexStr<SIZE, PAD1, PAD2, HEAP2>>>(iter: I) -> Self {
-        from_iter_str(iter)
+        from_iter_flex(iter)
     }
 }
 
@@ -462,16 +890,21 @@ where
     HEAP2: Deref<Target = str> + 'a,
 {
     /// ```
-    /// use flexstr::LocalStr;
+    /// use flexstr::{local_str, LocalStr};
     ///
     /// let v: Vec<LocalStr> = vec!["best".into(), "test".into()];
     /// let s: LocalStr = v.iter().filter(|s| *s == "best").collect();
     /// assert!(s.is_inline());
     /// assert_eq!(s, "best");
+    ///
+    /// // A single wrapped literal is not copied
+    /// let v = vec![local_str!("literal")];
+    /// let s: LocalStr = v.iter().collect();
+    /// assert!(s.is_static());
     /// ```
     #[inline]
     fn from_iter<I: IntoIterator<Item = &'a FlexStr<SIZE, PAD1, PAD2, HEAP2>>>(iter: I) -> Self {
-        from_iter_str(iter)
+        from_iter_flex(iter)
     }
 }
 
@@ -621,6 +1054,59 @@ where
 
 #[cfg(test)]
 mod tests {
+    // Each storage variant is checked against the `str` hash by `assert_storage_invariants`, so
+    // this only checks that equal strings hash the same across the aliases
+    macro_rules! hash_consistency_test {
+        ($name:ident, $type:ty) => {
+            #[test]
+            fn $name() {
+                use crate::hashed::hash_of;
+                use crate::{LocalStr, SharedStr};
+
+                for lit in ["", "inline", "☺☺☺", "This is too long to be inlined!!!"] {
+                    let s = <$type>::from_ref(lit);
+                    assert_eq!(hash_of(&s), hash_of(&LocalStr::from_ref(lit)), "{:#?}", s);
+                    assert_eq!(hash_of(&s), hash_of(&SharedStr::from_ref(lit)), "{:#?}", s);
+                }
+            }
+        };
+    }
+
+    hash_consistency_test!(local_str_hash_consistency, crate::LocalStr);
+    hash_consistency_test!(shared_str_hash_consistency, crate::SharedStr);
+    hash_consistency_test!(boxed_str_hash_consistency, crate::BoxedStr);
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn boxed_str_single_allocation() {
+        use crate::counting_alloc::count_allocations;
+        use crate::BoxedStr;
+        use alloc::boxed::Box;
+
+        let lit = "This is too long to be inlined!!!";
+
+        // Copying a str into heap storage is exactly one allocation
+        let allocations = count_allocations(|| {
+            let s = BoxedStr::from_ref(lit);
+            assert!(s.is_heap());
+        });
+        assert_eq!(allocations, 1);
+
+        // Wrapping an existing box doesn't allocate at all
+        let boxed: Box<str> = lit.into();
+        let allocations = count_allocations(|| {
+            let s: BoxedStr = boxed.into();
+            assert_eq!(s, lit);
+        });
+        assert_eq!(allocations, 0);
+
+        // Short strings are still inlined
+        let allocations = count_allocations(|| {
+            assert!(BoxedStr::from_ref("inlined").is_inline());
+        });
+        assert_eq!(allocations, 0);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serialization() {
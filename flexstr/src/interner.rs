@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use crate::SharedStr;
+
+/// A pool of [SharedStr] values that ensures strings with identical contents share a single heap
+/// allocation. Strings that are empty or short enough to be inlined bypass the pool entirely as
+/// they never allocate.
+/// ```
+/// use std::sync::Arc;
+/// use flexstr::Interner;
+///
+/// let mut interner = Interner::new();
+/// let a = interner.intern("This is too long to be inlined!!!");
+/// let b = interner.intern(&"This is too long to be inlined!!!".to_string());
+/// assert!(Arc::ptr_eq(&a.try_to_heap().unwrap(), &b.try_to_heap().unwrap()));
+/// assert_eq!(interner.len(), 1);
+///
+/// let c = interner.intern("inlined");
+/// assert!(c.is_inline());
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    pool: HashSet<SharedStr>,
+}
+
+impl Interner {
+    /// Creates a new empty interner
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a [SharedStr] with the contents of `s`. If a heap allocated string with identical
+    /// contents was previously interned, a clone of it is returned (a ref count increment),
+    /// otherwise a new string is created (and pooled if heap allocated).
+    pub fn intern(&mut self, s: &str) -> SharedStr {
+        if s.is_empty() {
+            return SharedStr::EMPTY;
+        }
+
+        match SharedStr::try_inline(s) {
+            Ok(s) => s,
+            Err(s) => match self.pool.get(s) {
+                Some(pooled) => pooled.clone(),
+                None => {
+                    let pooled = SharedStr::from_ref_heap(s);
+                    self.pool.insert(pooled.clone());
+                    pooled
+                }
+            },
+        }
+    }
+
+    /// Returns the number of heap allocated strings held by the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Returns true if the pool holds no strings
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    /// Removes all strings from the pool. Previously interned strings are unaffected.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.pool.clear()
+    }
+}
@@ -80,31 +80,58 @@ extern crate alloc;
 #[doc(hidden)]
 #[macro_use]
 pub mod builder;
+#[cfg(feature = "compact_str")]
+mod compact_str_support;
+#[cfg(all(test, feature = "std"))]
+mod counting_alloc;
+mod hashed;
 #[doc(hidden)]
 mod impls;
+#[cfg(feature = "std")]
+mod interner;
 mod macros;
+mod ops;
+#[cfg(feature = "regex")]
+mod regex_support;
 #[doc(hidden)]
 pub mod storage;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 #[doc(hidden)]
 pub mod traits;
+mod weak;
 
+use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
 use core::fmt::{Arguments, Write};
 use core::mem;
 use core::mem::ManuallyDrop;
 use core::ops::Deref;
+use core::str::Utf8Error;
 
 use static_assertions::{assert_eq_align, assert_eq_size, assert_impl_all, assert_not_impl_any};
 
+pub use crate::builder::ReusableBuilder;
+pub use crate::hashed::HashedStr;
+#[cfg(feature = "std")]
+pub use crate::interner::Interner;
+pub use crate::ops::{CharsIntoIter, ParseFlexError};
+#[cfg(feature = "regex")]
+pub use crate::regex_support::captures_flex;
 use crate::storage::heap::HeapStr;
 use crate::storage::inline::InlineFlexStr;
 pub use crate::storage::inline::STRING_SIZED_INLINE;
 use crate::storage::static_ref::StaticStr;
-pub use crate::storage::{StorageType, WrongStorageType};
+pub use crate::storage::{
+    HeapStorage, InvalidInlineArray, StorageType, TooLongForInlining, WrongStorageType,
+};
 #[doc(inline)]
 pub use crate::traits::*;
+pub use crate::weak::{WeakLocalStr, WeakSharedStr};
 
 // Trick to test README samples (from: https://github.com/rust-lang/cargo/issues/383#issuecomment-720873790)
 #[cfg(doctest)]
@@ -121,8 +148,15 @@ mod test_readme {
 
 assert_eq_size!(LocalStr, String);
 assert_eq_size!(SharedStr, String);
+assert_eq_size!(BoxedStr, String);
 assert_not_impl_any!(LocalStr: Send, Sync);
 assert_impl_all!(SharedStr: Send, Sync);
+assert_impl_all!(BoxedStr: Send, Sync);
+assert_impl_all!(HashedStr: Send, Sync);
+assert_impl_all!(WeakSharedStr: Send, Sync);
+assert_not_impl_any!(WeakLocalStr: Send, Sync);
+assert_impl_all!(CharsIntoIter<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Arc<str>>: Send, Sync);
+assert_not_impl_any!(CharsIntoIter<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Rc<str>>: Send, Sync);
 
 assert_eq_size!(HeapStr<PTR_SIZED_PAD, Rc<str>>, InlineFlexStr<STRING_SIZED_INLINE>);
 assert_eq_size!(StaticStr<PTR_SIZED_PAD>, InlineFlexStr<STRING_SIZED_INLINE>);
@@ -160,6 +194,8 @@ pub union FlexStr<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
 /// Custom concrete types need to specify a `HEAP` type with an exact size of two machine words (16 bytes
 /// on 64-bit, and 8 bytes on 32-bit). Any other sized parameter will result in a runtime panic on string
 /// creation.
+/// Use [assert_valid_layout](FlexStr::assert_valid_layout) in a `const` to check this at compile
+/// time instead.
 pub type FlexStrBase<HEAP> = FlexStr<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, HEAP>;
 
 /// A flexible string type that transparently wraps a string literal, inline string, or an [`Rc<str>`]
@@ -174,6 +210,16 @@ pub type LocalStr = FlexStrBase<Rc<str>>;
 /// Since this is just a type alias for a generic type, full documentation can be found here: [FlexStr]
 pub type SharedStr = FlexStrBase<Arc<str>>;
 
+/// A flexible string type that transparently wraps a string literal, inline string, or a [`Box<str>`]
+///
+/// Unlike [LocalStr] and [SharedStr] the heap storage is not reference counted, so it is cheaper to
+/// construct (a single allocation with no counts), but cloning a heap based string copies it.
+/// This makes it a good fit for strings that are built and owned in one place.
+///
+/// # Note
+/// Since this is just a type alias for a generic type, full documentation can be found here: [FlexStr]
+pub type BoxedStr = FlexStrBase<Box<str>>;
+
 // *** Clone ***
 
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Clone
@@ -252,7 +298,27 @@ where
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
     FlexStr<SIZE, PAD1, PAD2, HEAP>
 {
-    /// An empty ("") static constant string
+    /// An empty ("") static constant string. As this is defined on the generic type, it is
+    /// available on every alias and can be used in `const` contexts.
+    /// ```
+    /// use flexstr::{BoxedStr, LocalStr, SharedStr};
+    ///
+    /// struct Fields {
+    ///     local: LocalStr,
+    ///     shared: SharedStr,
+    ///     boxed: BoxedStr,
+    /// }
+    ///
+    /// const FIELDS: Fields = Fields {
+    ///     local: LocalStr::EMPTY,
+    ///     shared: SharedStr::EMPTY,
+    ///     boxed: BoxedStr::EMPTY,
+    /// };
+    ///
+    /// assert!(FIELDS.local.is_static() && FIELDS.local.is_empty());
+    /// assert!(FIELDS.shared.is_static() && FIELDS.shared.is_empty());
+    /// assert!(FIELDS.boxed.is_static() && FIELDS.boxed.is_empty());
+    /// ```
     pub const EMPTY: Self = if Self::IS_VALID_SIZE {
         FlexStr {
             static_str: StaticStr::EMPTY,
@@ -264,6 +330,31 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
     // If the union variants aren't the precise right size bad things will happen - we protect against that
     const IS_VALID_SIZE: bool = Self::variant_sizes_are_valid();
 
+    /// Checks the size and alignment invariants of a custom [FlexStr] type. This does nothing at
+    /// runtime, but when evaluated in a `const` it turns a type that would otherwise panic on first
+    /// use into a compile error.
+    /// ```
+    /// use flexstr::FlexStrBase;
+    /// use std::rc::Rc;
+    ///
+    /// type MyStr = FlexStrBase<Rc<str>>;
+    /// const _: () = MyStr::assert_valid_layout();
+    /// ```
+    ///
+    /// `String` is three machine words, so it can't be used as heap storage:
+    /// ```compile_fail
+    /// use flexstr::FlexStrBase;
+    ///
+    /// type MyStr = FlexStrBase<String>;
+    /// const _: () = MyStr::assert_valid_layout();
+    /// ```
+    #[inline]
+    pub const fn assert_valid_layout() {
+        if !Self::IS_VALID_SIZE {
+            panic!("{}", BAD_SIZE_OR_ALIGNMENT);
+        }
+    }
+
     #[inline]
     const fn variant_sizes_are_valid() -> bool {
         mem::size_of::<HeapStr<PAD2, HEAP>>() == mem::size_of::<InlineFlexStr<SIZE>>()
@@ -324,6 +415,72 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
         }
     }
 
+    /// Creates a new string from a byte slice after validating it is UTF-8. The string is inlined
+    /// when possible otherwise heap allocated. If the bytes are not valid UTF-8, a [Utf8Error] is
+    /// returned.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_utf8(b"test").unwrap();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "test");
+    ///
+    /// let err = LocalStr::from_utf8(b"te\xFFst").unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 2);
+    /// ```
+    #[inline]
+    pub fn from_utf8(bytes: &[u8]) -> Result<Self, Utf8Error>
+    where
+        HEAP: for<'a> From<&'a str>,
+    {
+        Ok(Self::from_ref(core::str::from_utf8(bytes)?))
+    }
+
+    /// Creates a new string from a byte slice, replacing any invalid UTF-8 sequences with
+    /// [REPLACEMENT_CHARACTER](char::REPLACEMENT_CHARACTER). The string is inlined when possible
+    /// otherwise heap allocated.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_utf8_lossy(b"te\xFFst");
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "te\u{FFFD}st");
+    ///
+    /// // A truncated sequence at the end is also replaced
+    /// assert_eq!(LocalStr::from_utf8_lossy(b"test\xE2\x82"), "test\u{FFFD}");
+    /// ```
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Self
+    where
+        HEAP: for<'a> From<&'a str>,
+    {
+        let mut buffer = buffer_new!(SIZE);
+        let mut builder = builder_new!(buffer, bytes.len());
+        let mut bytes = bytes;
+
+        loop {
+            match core::str::from_utf8(bytes) {
+                Ok(s) => {
+                    builder.str_write(s);
+                    break;
+                }
+                Err(err) => {
+                    let (valid, remaining) = bytes.split_at(err.valid_up_to());
+                    // SAFETY: We were just told these bytes are valid UTF-8
+                    builder.str_write(unsafe { core::str::from_utf8_unchecked(valid) });
+                    builder.char_write(char::REPLACEMENT_CHARACTER);
+
+                    match err.error_len() {
+                        Some(len) => bytes = &remaining[len..],
+                        // Unexpected end of input - nothing left to decode
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        builder_into!(builder, buffer)
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn from_inline(s: InlineFlexStr<SIZE>) -> Self {
@@ -402,6 +559,27 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
         unsafe { Self::try_inline(ch.encode_utf8(&mut [0; 4])).unwrap_unchecked() }
     }
 
+    // Creates a new string from a slice of this string's contents. A slice of a static string is
+    // re-sliced without copying and a slice spanning the whole string is just a clone, otherwise the
+    // slice is inlined when possible or heap allocated. `sub` MUST be a slice of this string.
+    #[inline]
+    fn sub_flex(&self, sub: &str) -> Self
+    where
+        HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+    {
+        if sub.len() == self.len() {
+            self.clone()
+        } else {
+            match self.try_as_static_str() {
+                Ok(literal) => {
+                    let start = sub.as_ptr() as usize - literal.as_ptr() as usize;
+                    Self::from_static(&literal[start..start + sub.len()])
+                }
+                Err(_) => Self::from_ref(sub),
+            }
+        }
+    }
+
     /// Returns the size of the maximum possible inline length for this type
     /// ```
     /// use flexstr::{LocalStr, STRING_SIZED_INLINE};
@@ -436,6 +614,27 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
         }
     }
 
+    /// Returns a copy of the inline string stored inside this [FlexStr], or `None` if this is not an
+    /// inlined string. As [InlineFlexStr] is `Copy`, this is useful in `Copy`-only contexts.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let inline = LocalStr::from_ref("inline").try_as_inline().unwrap();
+    /// assert_eq!(&*inline, "inline");
+    /// assert!(LocalStr::from_ref_heap("heap").try_as_inline().is_none());
+    /// assert!(LocalStr::from_static("static").try_as_inline().is_none());
+    /// ```
+    #[inline]
+    pub fn try_as_inline(&self) -> Option<InlineFlexStr<SIZE>> {
+        // SAFETY: Marker check is aligned to correct accessed field
+        unsafe {
+            match self.static_str.marker {
+                StorageType::Inline => Some(self.inline_str),
+                _ => None,
+            }
+        }
+    }
+
     /// Attempts to extract a copy of the heap value (for [LocalStr] this will be an [`Rc<str>`] and
     /// for [SharedStr] an [`Arc<str>`]) via cloning. If this is not a heap based string, a
     /// [WrongStorageType] error will be returned.
@@ -463,6 +662,103 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
         }
     }
 
+    /// Attempts to move the heap value out of this string (for [LocalStr] this will be an
+    /// [`Rc<str>`] and for [SharedStr] an [`Arc<str>`]) without cloning it. If this is not a heap
+    /// based string, the original string is returned as the error.
+    /// ```
+    /// use std::rc::Rc;
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let rc: Rc<str> = "test".into();
+    /// let s = LocalStr::from_heap(rc.clone());
+    /// assert!(Rc::ptr_eq(&s.try_into_heap().unwrap(), &rc));
+    ///
+    /// let s = local_str!("static");
+    /// assert_eq!(s.try_into_heap().unwrap_err(), "static");
+    /// ```
+    #[inline]
+    pub fn try_into_heap(self) -> Result<HEAP, Self> {
+        if self.is_heap() {
+            let mut s = ManuallyDrop::new(self);
+            // SAFETY: Marker check is aligned to correct accessed field and since `s` is never
+            // dropped, the heap value is only moved out of it once
+            unsafe { Ok(ManuallyDrop::take(&mut s.heap_str).heap) }
+        } else {
+            Err(self)
+        }
+    }
+
+    // Returns a reference to the heap value if this is a heap based string
+    #[inline]
+    pub(crate) fn as_heap(&self) -> Option<&HEAP> {
+        // SAFETY: Marker check is aligned to correct accessed field
+        unsafe {
+            match self.heap_str.marker {
+                StorageType::Heap => Some(&self.heap_str.heap),
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the contents if they are uniquely owned by this string,
+    /// allowing in-place edits without reallocation. Inline strings and heap strings with a single
+    /// strong reference return `Some`, while wrapped literals and shared heap storage return `None`.
+    /// Unlike [`Arc::make_mut`](alloc::sync::Arc::make_mut), shared contents are never cloned.
+    /// ```
+    /// use flexstr::{shared_str, SharedStr};
+    ///
+    /// let mut s = shared_str!("static");
+    /// assert!(s.make_mut().is_none());
+    ///
+    /// let mut s = SharedStr::from_ref("inline");
+    /// s.make_mut().unwrap().make_ascii_uppercase();
+    /// assert_eq!(s, "INLINE");
+    ///
+    /// let mut s = SharedStr::from_ref_heap("heap");
+    /// let s2 = s.clone();
+    /// assert!(s.make_mut().is_none());
+    /// drop(s2);
+    /// s.make_mut().unwrap().make_ascii_uppercase();
+    /// assert_eq!(s, "HEAP");
+    /// ```
+    #[inline]
+    pub fn make_mut(&mut self) -> Option<&mut str>
+    where
+        HEAP: HeapStorage,
+    {
+        // SAFETY: Marker check is aligned to correct accessed field
+        unsafe {
+            match self.static_str.marker {
+                StorageType::Static => None,
+                StorageType::Inline => Some(self.inline_str.as_mut_str()),
+                StorageType::Heap => self.heap_str.heap.get_mut(),
+            }
+        }
+    }
+
+    /// Returns the number of strong references to the heap storage of this string, or `None` if
+    /// this is not a heap based string (static and inline strings are not reference counted)
+    /// ```
+    /// use flexstr::{local_str, SharedStr};
+    ///
+    /// let s = SharedStr::from_ref("This is too long to be inlined!!!");
+    /// assert_eq!(s.ref_count(), Some(1));
+    /// let s2 = s.clone();
+    /// assert_eq!(s.ref_count(), Some(2));
+    /// drop(s2);
+    /// assert_eq!(s.ref_count(), Some(1));
+    ///
+    /// assert_eq!(local_str!("static").ref_count(), None);
+    /// assert_eq!(SharedStr::from_ref("inline").ref_count(), None);
+    /// ```
+    #[inline]
+    pub fn ref_count(&self) -> Option<usize>
+    where
+        HEAP: HeapStorage,
+    {
+        self.as_heap().map(HeapStorage::strong_count)
+    }
+
     /// Returns a copy of the heap value (for [FlexStr] this will be an [`Rc<str>`] and
     /// for [SharedStr] an [`Arc<str>`]). If this is not a heap based string, a new value will be allocated
     /// and returned
@@ -484,6 +780,20 @@ impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
         }
     }
 
+    /// Returns the type of storage used by this string
+    /// ```
+    /// use flexstr::{local_str, LocalStr, StorageType};
+    ///
+    /// assert_eq!(local_str!("test").storage_type(), StorageType::Static);
+    /// assert_eq!(LocalStr::try_inline("test").unwrap().storage_type(), StorageType::Inline);
+    /// assert_eq!(LocalStr::from_ref_heap("test").storage_type(), StorageType::Heap);
+    /// ```
+    #[inline]
+    pub fn storage_type(&self) -> StorageType {
+        // SAFETY: Marker is identical in all union fields
+        unsafe { self.static_str.marker }
+    }
+
     /// Returns true if this is a wrapped string literal (`&'static str`)
     /// ```
     /// use flexstr::LocalStr;
@@ -561,6 +871,21 @@ where
         }
     }
 
+    /// Returns the number of chars (Unicode scalar values) in this [FlexStr]. Unlike
+    /// [len](FlexStr::len), this is not the length in bytes, and requires scanning the string.
+    /// ```
+    /// use flexstr::local_str;
+    ///
+    /// let s = local_str!("😀");
+    /// assert_eq!((s.len(), s.char_count()), (4, 1));
+    /// assert_eq!(local_str!("aü☺😀").char_count(), 4);
+    /// assert_eq!(local_str!("").char_count(), 0);
+    /// ```
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.chars().count()
+    }
+
     /// Extracts a string slice containing the entire [FlexStr]
     /// ```
     /// use flexstr::ToLocalStr;
@@ -573,6 +898,34 @@ where
         self
     }
 
+    /// Returns this string as an [OsStr](std::ffi::OsStr) without copying
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use flexstr::local_str;
+    ///
+    /// assert_eq!(local_str!("abc").as_os_str(), OsStr::new("abc"));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn as_os_str(&self) -> &std::ffi::OsStr {
+        std::ffi::OsStr::new(self.as_str())
+    }
+
+    /// Returns this string as a [Path](std::path::Path) without copying
+    /// ```
+    /// use std::path::Path;
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_ref_heap("dir/file.txt");
+    /// assert_eq!(s.as_path(), Path::new("dir/file.txt"));
+    /// assert_eq!(s.as_path().extension().unwrap(), "txt");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn as_path(&self) -> &std::path::Path {
+        std::path::Path::new(self.as_str())
+    }
+
     /// Converts this [FlexStr] into a [String]. This should be more efficient than using the [ToString]
     /// trait (which we cannot implement due to a blanket stdlib implementation) as this avoids the
     /// [Display](alloc::fmt::Display)-based implementation.
@@ -587,6 +940,92 @@ where
         String::from(&**self)
     }
 
+    /// Appends the bytes of this string to `v` without any intermediate allocation
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let mut v = Vec::new();
+    /// local_str!("abc").extend_into(&mut v);
+    /// LocalStr::from_ref("def").extend_into(&mut v);
+    /// LocalStr::from_ref_heap("ghi").extend_into(&mut v);
+    /// assert_eq!(v, b"abcdefghi");
+    /// ```
+    #[inline]
+    pub fn extend_into(&self, v: &mut Vec<u8>) {
+        v.extend_from_slice(self.as_bytes());
+    }
+
+    /// Writes the bytes of this string to `w` without any intermediate allocation
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let mut v = Vec::new();
+    /// local_str!("abc").write_to(&mut v).unwrap();
+    /// LocalStr::from_ref("def").write_to(&mut v).unwrap();
+    /// LocalStr::from_ref_heap("ghi").write_to(&mut v).unwrap();
+    /// assert_eq!(v, b"abcdefghi");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(self.as_bytes())
+    }
+
+    /// Returns an inlined copy of this string if it is heap allocated and short enough to be inlined,
+    /// otherwise it is returned unchanged. This can be used to regain the benefits of inlining after
+    /// a short string was forced into heap storage (ex: via [from_heap](FlexStr::from_heap)).
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_ref_heap("test").optimize();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "test");
+    ///
+    /// let s = LocalStr::from_ref_heap("This is too long to be inlined!!!!!!!").optimize();
+    /// assert!(s.is_heap());
+    /// ```
+    #[inline]
+    pub fn optimize(self) -> Self {
+        if self.is_heap() {
+            match InlineFlexStr::try_new(self.as_str()) {
+                Ok(s) => Self::from_inline(s),
+                Err(_) => self,
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Returns this string with storage whose data pointer does not change when the string is moved.
+    /// Static and heap strings are returned unchanged, while inlined strings are copied to the
+    /// heap.
+    ///
+    /// # Note
+    /// The pointer returned by `as_ptr` for an inlined string points into the string value itself, so
+    /// it is only valid for as long as the string is not moved or dropped. Use this before handing a
+    /// `(ptr, len)` pair to foreign code that may hold onto it while the string is moved.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_ref("inlined").into_ffi_stable();
+    /// assert!(s.is_heap());
+    /// let (ptr, len) = (s.as_ptr(), s.len());
+    ///
+    /// let moved = Box::new(s);
+    /// assert_eq!((moved.as_ptr(), moved.len()), (ptr, len));
+    /// ```
+    #[inline]
+    pub fn into_ffi_stable(self) -> Self
+    where
+        HEAP: for<'a> From<&'a str>,
+    {
+        if self.is_inline() {
+            Self::from_ref_heap(self.as_str())
+        } else {
+            self
+        }
+    }
+
     #[inline]
     fn add(mut self, rhs: &str) -> Self
     where
@@ -653,6 +1092,38 @@ where
     builder_into!(builder, buffer)
 }
 
+// Like `from_iter_str`, but a single wrapped literal is rewrapped instead of copied
+#[inline]
+fn from_iter_flex<const SIZE: usize, const PAD1: usize, const PAD2: usize, I, HEAP, HEAP2, U>(
+    iter: I,
+) -> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    I: IntoIterator<Item = U>,
+    HEAP: for<'b> From<&'b str>,
+    HEAP2: Deref<Target = str>,
+    U: Borrow<FlexStr<SIZE, PAD1, PAD2, HEAP2>>,
+{
+    let mut iter = iter.into_iter();
+
+    let first = match iter.next() {
+        Some(first) => first,
+        None => return FlexStr::EMPTY,
+    };
+    let second = iter.next();
+
+    match (first.borrow().try_as_static_str(), &second) {
+        (Ok(literal), None) => FlexStr::from_static(literal),
+        _ => {
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer);
+            for s in core::iter::once(first).chain(second).chain(iter) {
+                builder.str_write(s.borrow().as_str());
+            }
+            builder_into!(builder, buffer)
+        }
+    }
+}
+
 #[inline]
 fn from_iter_char<const SIZE: usize, const PAD1: usize, const PAD2: usize, I, F, HEAP, U>(
     iter: I,
@@ -6,6 +6,14 @@
 /// const STR: LocalStr = local_str!("This is a constant!");
 /// assert!(STR.is_static())
 /// ```
+///
+/// Only `&'static str` values can be wrapped, so passing a borrowed `String` fails to compile:
+/// ```compile_fail
+/// use flexstr::{local_str, LocalStr};
+///
+/// let s = String::from("Not a literal");
+/// let s: LocalStr = local_str!(&s);
+/// ```
 #[macro_export]
 macro_rules! local_str {
     ($str:expr) => {
@@ -28,6 +36,21 @@ macro_rules! shared_str {
     };
 }
 
+/// Create compile time constant [BoxedStr](crate::BoxedStr) (equivalent, but less typing than:
+/// `BoxedStr::from_static("my_literal")`
+/// ```
+/// use flexstr::{boxed_str, BoxedStr};
+///
+/// const STR: BoxedStr = boxed_str!("This is a constant!");
+/// assert!(STR.is_static())
+/// ```
+#[macro_export]
+macro_rules! boxed_str {
+    ($str:expr) => {
+        <$crate::BoxedStr>::from_static($str)
+    };
+}
+
 /// Equivalent to [local_fmt] except that it uses `ufmt` which is much faster, but has limitations.
 /// See [ufmt docs](https://docs.rs/ufmt/latest/ufmt/) for more details
 /// ```
@@ -0,0 +1,1599 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::iter;
+use core::iter::FusedIterator;
+use core::ops::Deref;
+use core::slice::SliceIndex;
+use core::str::FromStr;
+
+use crate::storage::inline::InlineFlexStr;
+use crate::{FindPattern, FlexStr, HeapStorage, ToCase};
+
+// *** Parse Error ***
+
+/// Error type returned from [parse_flex](FlexStr::parse_flex) containing both the string that could
+/// not be parsed and the error returned by [FromStr]
+#[derive(Clone, Debug)]
+pub struct ParseFlexError<S, E> {
+    /// The string that could not be parsed
+    pub input: S,
+    /// The error returned from parsing
+    pub error: E,
+}
+
+impl<S, E> Display for ParseFlexError<S, E>
+where
+    S: Debug,
+    E: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Unable to parse ")?;
+        self.input.fmt(f)?;
+        f.write_str(": ")?;
+        self.error.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, E> std::error::Error for ParseFlexError<S, E>
+where
+    S: Debug,
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+// *** Slicing ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    /// Returns a new [FlexStr] containing the given range of this string or `None` if the range is
+    /// out of bounds or does not fall on a char boundary. This is the non-panicking equivalent of
+    /// indexing. Slices of a wrapped string literal are also wrapped literals (no copy), otherwise
+    /// the result is inlined when possible. Use [str::get] (via [Deref]) to borrow a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("Hello, world!");
+    /// let hello = S.get_flex(..5).unwrap();
+    /// assert!(hello.is_static());
+    /// assert_eq!(hello, "Hello");
+    ///
+    /// assert!(S.get_flex(5..100).is_none());
+    ///
+    /// let s: LocalStr = "☺☺".into();
+    /// assert_eq!(s.get_flex(3..).unwrap(), "☺");
+    /// assert!(s.get_flex(1..).is_none());
+    ///
+    /// let borrowed: Option<&str> = s.get(..3);
+    /// assert_eq!(borrowed, Some("☺"));
+    /// ```
+    #[inline]
+    pub fn get_flex<I>(&self, index: I) -> Option<Self>
+    where
+        I: SliceIndex<str, Output = str>,
+    {
+        str::get(self, index).map(|s| self.sub_flex(s))
+    }
+
+    /// Splits this string after the first `n` chars (not bytes) returning both parts as new
+    /// [FlexStr] (wrapped literal or inlined when possible). If `n` exceeds the number of chars the
+    /// whole string is returned followed by an empty string.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a☺ü");
+    /// let (front, back) = S.split_at_char(2);
+    /// assert!(front.is_static() && back.is_static());
+    /// assert_eq!((front, back), (local_str!("a☺"), local_str!("ü")));
+    ///
+    /// assert_eq!(S.split_at_char(0), ("".into(), S));
+    /// assert_eq!(S.split_at_char(3), (S, "".into()));
+    /// assert_eq!(S.split_at_char(100), (S, "".into()));
+    /// ```
+    #[inline]
+    pub fn split_at_char(&self, n: usize) -> (Self, Self) {
+        let idx = self
+            .char_indices()
+            .nth(n)
+            .map_or(self.len(), |(idx, _)| idx);
+        let (front, back) = str::split_at(self, idx);
+        (self.sub_flex(front), self.sub_flex(back))
+    }
+
+    /// Splits this string on the first match of `pat` returning the parts before and after it, or
+    /// `None` if there is no match. Each part is returned as a new [FlexStr] (wrapped literal or
+    /// inlined when possible). See [FindPattern] for the types of patterns accepted. Use
+    /// [str::split_once] (via [Deref]) to borrow both parts as `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let (k, v) = local_str!("k=v").split_once_flex('=').unwrap();
+    /// assert!(k.is_static() && v.is_static());
+    /// assert_eq!((k, v), (local_str!("k"), local_str!("v")));
+    ///
+    /// assert_eq!(local_str!("=v").split_once_flex('='), Some(("".into(), "v".into())));
+    /// assert_eq!(local_str!("k=").split_once_flex('='), Some(("k".into(), "".into())));
+    /// assert_eq!(local_str!("k=v=w").split_once_flex('='), Some(("k".into(), "v=w".into())));
+    /// assert_eq!(local_str!("kv").split_once_flex('='), None);
+    /// assert_eq!(local_str!("k: v: w").split_once_flex(": "), Some(("k".into(), "v: w".into())));
+    /// assert_eq!(local_str!("k v").split_once_flex(char::is_whitespace), Some(("k".into(), "v".into())));
+    ///
+    /// let s: LocalStr = "k=v".into();
+    /// let (k, v): (&str, &str) = s.split_once('=').unwrap();
+    /// assert_eq!((k, v), ("k", "v"));
+    /// ```
+    #[inline]
+    pub fn split_once_flex<P: FindPattern>(&self, mut pat: P) -> Option<(Self, Self)> {
+        pat.match_in(self)
+            .map(|(start, end)| (self.sub_flex(&self[..start]), self.sub_flex(&self[end..])))
+    }
+
+    /// Splits this string on the last match of `pat` returning the parts before and after it, or
+    /// `None` if there is no match. Each part is returned as a new [FlexStr] (wrapped literal or
+    /// inlined when possible). See [FindPattern] for the types of patterns accepted. Use
+    /// [str::rsplit_once] (via [Deref]) to borrow both parts as `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let (k, v) = local_str!("k=v").rsplit_once_flex('=').unwrap();
+    /// assert!(k.is_static() && v.is_static());
+    /// assert_eq!((k, v), (local_str!("k"), local_str!("v")));
+    ///
+    /// assert_eq!(local_str!("=v").rsplit_once_flex('='), Some(("".into(), "v".into())));
+    /// assert_eq!(local_str!("k=").rsplit_once_flex('='), Some(("k".into(), "".into())));
+    /// assert_eq!(local_str!("k=v=w").rsplit_once_flex('='), Some(("k=v".into(), "w".into())));
+    /// assert_eq!(local_str!("kv").rsplit_once_flex('='), None);
+    /// assert_eq!(local_str!("a::b::c").rsplit_once_flex("::"), Some(("a::b".into(), "c".into())));
+    ///
+    /// let s: LocalStr = "k=v=w".into();
+    /// let (k, v): (&str, &str) = s.rsplit_once('=').unwrap();
+    /// assert_eq!((k, v), ("k=v", "w"));
+    /// ```
+    #[inline]
+    pub fn rsplit_once_flex<P: FindPattern>(&self, mut pat: P) -> Option<(Self, Self)> {
+        pat.rmatch_in(self)
+            .map(|(start, end)| (self.sub_flex(&self[..start]), self.sub_flex(&self[end..])))
+    }
+}
+
+// *** Searching ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Returns true if `pat` matches anywhere in this string. See [FindPattern] for the types of
+    /// patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("inlined");
+    /// assert!(S.contains(&local_str!("line")));
+    /// assert!(S.contains(""));
+    /// assert!(S.contains('d'));
+    /// assert!(!S.contains(char::is_whitespace));
+    /// assert!(!S.contains("outline"));
+    ///
+    /// const NEEDLES: [&str; 3] = ["out", "line", "in"];
+    /// assert!(NEEDLES.iter().any(|needle| S.contains(needle)));
+    /// assert_eq!(NEEDLES.iter().position(|needle| S.starts_with(needle)), Some(2));
+    /// ```
+    #[inline]
+    pub fn contains<P: FindPattern>(&self, mut pat: P) -> bool {
+        pat.contained_in(self)
+    }
+
+    /// Returns true if `pat` matches at the start of this string. See [FindPattern] for the types
+    /// of patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("inlined");
+    /// assert!(S.starts_with(&local_str!("in")));
+    /// assert!(S.starts_with(""));
+    /// assert!(S.starts_with('i'));
+    /// assert!(S.starts_with(|ch: char| ch.is_ascii_lowercase()));
+    /// assert!(!S.starts_with("lined"));
+    /// ```
+    #[inline]
+    pub fn starts_with<P: FindPattern>(&self, mut pat: P) -> bool {
+        pat.prefix_of(self)
+    }
+
+    /// Returns true if `pat` matches at the end of this string. See [FindPattern] for the types of
+    /// patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("inlined");
+    /// assert!(S.ends_with(&local_str!("lined")));
+    /// assert!(S.ends_with(""));
+    /// assert!(S.ends_with('d'));
+    /// assert!(!S.ends_with(char::is_uppercase));
+    /// assert!(!S.ends_with("in"));
+    /// ```
+    #[inline]
+    pub fn ends_with<P: FindPattern>(&self, mut pat: P) -> bool {
+        pat.suffix_of(self)
+    }
+
+    /// Returns the byte index of the start of the first match of `pat` or `None` if there is no
+    /// match. See [FindPattern] for the types of patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a=b=c");
+    /// assert_eq!(S.find('='), Some(1));
+    /// assert_eq!(S.find("=c"), Some(3));
+    /// assert_eq!(S.find(&"b".to_string()), Some(2));
+    /// assert_eq!(S.find(&local_str!("c")), Some(4));
+    /// assert_eq!(S.find(|ch: char| ch != 'a'), Some(1));
+    /// assert_eq!(S.find(&['c', 'b'][..]), Some(2));
+    /// assert_eq!(S.find(['c', 'b']), Some(2));
+    /// assert_eq!(S.find(&['c', 'b']), Some(2));
+    /// assert_eq!(S.find(';'), None);
+    /// ```
+    #[inline]
+    pub fn find<P: FindPattern>(&self, mut pat: P) -> Option<usize> {
+        pat.find_in(self)
+    }
+
+    /// Returns the byte index of the start of the last match of `pat` or `None` if there is no
+    /// match. See [FindPattern] for the types of patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a=b=c");
+    /// assert_eq!(S.rfind('='), Some(3));
+    /// assert_eq!(S.rfind("a="), Some(0));
+    /// assert_eq!(S.rfind(&"b".to_string()), Some(2));
+    /// assert_eq!(S.rfind(&local_str!("=")), Some(3));
+    /// assert_eq!(S.rfind(|ch: char| ch != 'c'), Some(3));
+    /// assert_eq!(S.rfind("missing"), None);
+    /// ```
+    #[inline]
+    pub fn rfind<P: FindPattern>(&self, mut pat: P) -> Option<usize> {
+        pat.rfind_in(self)
+    }
+
+    /// Returns the number of non-overlapping matches of `pat` (as found by [str::matches]). An empty
+    /// `&str` pattern matches at every char boundary, so it counts one more than the number of
+    /// chars. See [FindPattern] for the types of patterns accepted.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("aaa");
+    /// assert_eq!(S.count_matches("aa"), 1);
+    /// assert_eq!(S.count_matches('a'), 3);
+    /// assert_eq!(S.count_matches(&local_str!("a")), 3);
+    /// assert_eq!(S.count_matches('b'), 0);
+    /// assert_eq!(local_str!("a,b;c").count_matches(&[',', ';'][..]), 2);
+    /// assert_eq!(S.count_matches(""), 4);
+    /// ```
+    #[inline]
+    pub fn count_matches<P: FindPattern>(&self, mut pat: P) -> usize {
+        pat.count_in(self)
+    }
+
+    /// Returns true if this string is equal to `other` ignoring ASCII case. Non-ASCII chars must
+    /// match exactly.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("Content-Type");
+    /// assert!(S.eq_ignore_ascii_case("content-type"));
+    /// assert!(S.eq_ignore_ascii_case(String::from("CONTENT-TYPE")));
+    /// assert!(!S.eq_ignore_ascii_case("Content-Typ"));
+    ///
+    /// assert!(!local_str!("Ünïcode").eq_ignore_ascii_case("ÜNÏCODE"));
+    /// assert!(local_str!("Ünïcode").eq_ignore_ascii_case("ÜnïCODE"));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_ascii_case(&self, other: impl AsRef<str>) -> bool {
+        str::eq_ignore_ascii_case(self, other.as_ref())
+    }
+
+    /// Returns true if `prefix` is a prefix of this string ignoring ASCII case. Non-ASCII chars must
+    /// match exactly.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("Content-Type");
+    /// assert!(S.starts_with_ignore_ascii_case("CONTENT-"));
+    /// assert!(S.starts_with_ignore_ascii_case(""));
+    /// assert!(!S.starts_with_ignore_ascii_case("Content-Type: "));
+    /// assert!(!local_str!("ü").starts_with_ignore_ascii_case("Ü"));
+    /// ```
+    #[inline]
+    pub fn starts_with_ignore_ascii_case(&self, prefix: impl AsRef<str>) -> bool {
+        let prefix = prefix.as_ref().as_bytes();
+
+        match self.as_bytes().get(..prefix.len()) {
+            Some(bytes) => bytes.eq_ignore_ascii_case(prefix),
+            None => false,
+        }
+    }
+
+    /// Returns true if any of `chars` appears in this string. The string is scanned only once,
+    /// with each of its chars compared against the set, so this is O(n * m) in the worst case, but
+    /// avoids rescanning the string for each needle.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("file name.txt");
+    /// assert!(S.contains_any(&['/', ' ']));
+    /// assert!(!S.contains_any(&['/', '\\']));
+    /// assert!(!S.contains_any(&[]));
+    /// assert!(!local_str!("").contains_any(&['a']));
+    /// ```
+    #[inline]
+    pub fn contains_any(&self, chars: &[char]) -> bool {
+        str::contains(self, chars)
+    }
+
+    /// Returns the index of the first of `prefixes` that this string starts with, or `None` if it
+    /// starts with none of them. Prefixes are checked in order, so more specific prefixes should be
+    /// listed first. An empty prefix always matches.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("https://example.com");
+    /// assert_eq!(S.starts_with_any(&["http://", "https://", "http"]), Some(1));
+    /// assert_eq!(S.starts_with_any(&["ftp://"]), None);
+    /// assert_eq!(S.starts_with_any(&[]), None);
+    /// assert_eq!(S.starts_with_any(&["ftp://", ""]), Some(1));
+    /// ```
+    #[inline]
+    pub fn starts_with_any(&self, prefixes: &[&str]) -> Option<usize> {
+        prefixes
+            .iter()
+            .position(|prefix| str::starts_with(self, prefix))
+    }
+
+    /// Returns the index of the first of `suffixes` that this string ends with, or `None` if it ends
+    /// with none of them. Suffixes are checked in order, so more specific suffixes should be listed
+    /// first. An empty suffix always matches.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const SUFFIXES: [&str; 3] = [".tar.gz", ".gz", ".tar"];
+    /// assert_eq!(local_str!("file.tar.gz").ends_with_any(&SUFFIXES), Some(0));
+    /// assert_eq!(local_str!("file.gz").ends_with_any(&SUFFIXES), Some(1));
+    /// assert_eq!(local_str!("file.zip").ends_with_any(&SUFFIXES), None);
+    /// assert_eq!(local_str!("file.zip").ends_with_any(&[]), None);
+    /// assert_eq!(local_str!("file.zip").ends_with_any(&[""]), Some(0));
+    /// ```
+    #[inline]
+    pub fn ends_with_any(&self, suffixes: &[&str]) -> Option<usize> {
+        suffixes
+            .iter()
+            .position(|suffix| str::ends_with(self, suffix))
+    }
+}
+
+// *** Trimming ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    /// Returns a new [FlexStr] with all leading and trailing matches of `pat` removed. Trimming a
+    /// wrapped string literal results in a wrapped literal (no copy) and trimming nothing results
+    /// in a clone, otherwise the result is inlined when possible. See [FindPattern] for the types of
+    /// patterns accepted. Use [str::trim_matches] (via [Deref]) to borrow a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("123abc456");
+    /// let s = S.trim_matches_flex(|ch: char| ch.is_ascii_digit());
+    /// assert!(s.is_static());
+    /// assert_eq!(s, "abc");
+    ///
+    /// assert_eq!(S.trim_matches_flex(|_| true), "");
+    /// assert_eq!(S.trim_matches_flex(|_| false), S);
+    /// assert_eq!(local_str!("\"quoted\"").trim_matches_flex('"'), "quoted");
+    ///
+    /// let s: LocalStr = "kvk".into();
+    /// let borrowed: &str = s.trim_matches('k');
+    /// assert_eq!(borrowed, "v");
+    /// ```
+    #[inline]
+    pub fn trim_matches_flex<P: FindPattern>(&self, mut pat: P) -> Self {
+        let trimmed = pat.trim_start_of(self);
+        self.sub_flex(pat.trim_end_of(trimmed))
+    }
+
+    /// Returns a new [FlexStr] with all leading matches of `pat` removed. Trimming a wrapped string
+    /// literal results in a wrapped literal (no copy) and trimming nothing results in a clone,
+    /// otherwise the result is inlined when possible. See [FindPattern] for the types of patterns
+    /// accepted. Use [str::trim_start_matches] (via [Deref]) to borrow a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("123abc456");
+    /// assert_eq!(S.trim_start_matches_flex(|ch: char| ch.is_ascii_digit()), "abc456");
+    /// assert_eq!(S.trim_start_matches_flex('1'), "23abc456");
+    /// assert_eq!(S.trim_start_matches_flex("12"), "3abc456");
+    /// ```
+    #[inline]
+    pub fn trim_start_matches_flex<P: FindPattern>(&self, mut pat: P) -> Self {
+        self.sub_flex(pat.trim_start_of(self))
+    }
+
+    /// Returns a new [FlexStr] with all trailing matches of `pat` removed. Trimming a wrapped string
+    /// literal results in a wrapped literal (no copy) and trimming nothing results in a clone,
+    /// otherwise the result is inlined when possible. See [FindPattern] for the types of patterns
+    /// accepted. Use [str::trim_end_matches] (via [Deref]) to borrow a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("123abc456");
+    /// assert_eq!(S.trim_end_matches_flex(|ch: char| ch.is_ascii_digit()), "123abc");
+    /// assert_eq!(S.trim_end_matches_flex('6'), "123abc45");
+    /// assert_eq!(S.trim_end_matches_flex("56"), "123abc4");
+    /// ```
+    #[inline]
+    pub fn trim_end_matches_flex<P: FindPattern>(&self, mut pat: P) -> Self {
+        self.sub_flex(pat.trim_end_of(self))
+    }
+}
+
+// *** Validation ***
+
+// Max length of a DNS name (excluding the optional trailing dot) and of each label (RFC 1123)
+const MAX_HOSTNAME_LEN: usize = 253;
+const MAX_LABEL_LEN: usize = 63;
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Returns true if every char in this string is ASCII (an empty string is ASCII). This scans
+    /// the underlying bytes and is used to select ASCII fast paths elsewhere.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// assert!(local_str!("ascii").is_ascii());
+    /// assert!(!local_str!("naïve").is_ascii());
+    /// assert!(LocalStr::EMPTY.is_ascii());
+    /// ```
+    #[inline]
+    pub fn is_ascii(&self) -> bool {
+        self.as_bytes().is_ascii()
+    }
+
+    /// Returns true if this string is a valid hostname per RFC 1123. The name must be no longer than
+    /// 253 bytes (not counting an optional trailing dot) and made up of dot separated labels of 1 to
+    /// 63 ASCII letters, digits, or hyphens that do not begin or end with a hyphen.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// for (name, valid) in [
+    ///     ("localhost", true),
+    ///     ("www.example.com", true),
+    ///     ("www.example.com.", true),
+    ///     ("123.example-1.com", true),
+    ///     ("", false),
+    ///     (".", false),
+    ///     ("www..example.com", false),
+    ///     ("-www.example.com", false),
+    ///     ("www-.example.com", false),
+    ///     ("www_example.com", false),
+    ///     ("wörld.com", false),
+    /// ] {
+    ///     assert_eq!(LocalStr::from(name).validate_hostname(), valid, "{}", name);
+    /// }
+    ///
+    /// assert!(!LocalStr::from("a".repeat(64)).validate_hostname());
+    /// assert!(LocalStr::from(["a"; 127].join(".")).validate_hostname());
+    /// assert!(!LocalStr::from(["a"; 128].join(".")).validate_hostname());
+    /// ```
+    pub fn validate_hostname(&self) -> bool {
+        let name = self.strip_suffix('.').unwrap_or(self);
+
+        !name.is_empty()
+            && name.len() <= MAX_HOSTNAME_LEN
+            && name.split('.').all(|label| {
+                let bytes = label.as_bytes();
+
+                match (bytes.first(), bytes.last()) {
+                    (Some(first), Some(last)) => {
+                        bytes.len() <= MAX_LABEL_LEN
+                            && *first != b'-'
+                            && *last != b'-'
+                            && bytes
+                                .iter()
+                                .all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+                    }
+                    // Empty label
+                    _ => false,
+                }
+            })
+    }
+}
+
+// *** Parsing ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    /// Parses this string into another type. This is identical to the [str] equivalent.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("123");
+    /// assert_eq!(S.parse::<u32>().unwrap(), 123);
+    /// assert!(local_str!("abc").parse::<u32>().is_err());
+    /// ```
+    #[inline]
+    pub fn parse<T: FromStr>(&self) -> Result<T, T::Err> {
+        str::parse(self)
+    }
+
+    /// Parses this string into another type. On failure, a [ParseFlexError] is returned that
+    /// contains both a clone of this string (for context) and the error returned by [FromStr].
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("abc");
+    /// let err = S.parse_flex::<u32>().unwrap_err();
+    /// assert_eq!(err.input, "abc");
+    /// assert_eq!(err.error, "abc".parse::<u32>().unwrap_err());
+    /// assert_eq!(err.to_string(), r#"Unable to parse "abc": invalid digit found in string"#);
+    /// ```
+    #[inline]
+    pub fn parse_flex<T: FromStr>(&self) -> Result<T, ParseFlexError<Self, T::Err>>
+    where
+        HEAP: Clone,
+    {
+        str::parse(self).map_err(|error| ParseFlexError {
+            input: self.clone(),
+            error,
+        })
+    }
+}
+
+// *** Building ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    /// Returns a new [FlexStr] with `s` inserted at byte position `idx`. The result is inlined when
+    /// possible. Inserting an empty string results in a clone.
+    ///
+    /// # Panics
+    /// Panics if `idx` is larger than the length of this string or does not lie on a char boundary
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("inline");
+    /// let s = S.inserted(2, "-");
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "in-line");
+    ///
+    /// assert_eq!(S.inserted(0, "<"), "<inline");
+    /// assert_eq!(S.inserted(S.len(), ">"), "inline>");
+    /// assert!(S.inserted(3, "").is_static());
+    ///
+    /// let s = S.inserted(6, " strings are no longer inlined when this long");
+    /// assert!(s.is_heap());
+    /// ```
+    pub fn inserted(&self, idx: usize, s: &str) -> Self {
+        assert!(
+            self.is_char_boundary(idx),
+            "index is not on a char boundary"
+        );
+
+        if s.is_empty() {
+            self.clone()
+        } else {
+            let (front, back) = self.split_at(idx);
+
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer, self.len() + s.len());
+            builder.str_write(front);
+            builder.str_write(s);
+            builder.str_write(back);
+            builder_into!(builder, buffer)
+        }
+    }
+
+    /// Returns a new [FlexStr] containing the concatenation of `parts`. The total length is summed
+    /// first so the result is either inlined or heap allocated exactly once at the exact size.
+    /// ```
+    /// use flexstr::{local_str, LocalStr, SharedStr};
+    ///
+    /// let s = LocalStr::concat(&[local_str!("in"), local_str!("line")]);
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "inline");
+    ///
+    /// let parts = vec!["This string is "; 10];
+    /// let s = SharedStr::concat(&parts);
+    /// assert!(s.is_heap());
+    /// assert_eq!(s, parts.concat());
+    ///
+    /// assert!(LocalStr::concat::<&str>(&[]).is_static());
+    /// ```
+    pub fn concat<S: AsRef<str>>(parts: &[S]) -> Self
+    where
+        HEAP: HeapStorage,
+    {
+        let len = parts.iter().map(|part| part.as_ref().len()).sum();
+
+        if len == 0 {
+            Self::EMPTY
+        } else if len <= SIZE {
+            let mut s = InlineFlexStr::try_new("").expect("Empty string always fits");
+            for part in parts {
+                s.try_push_str(part.as_ref())
+                    .expect("Length checked to fit inline");
+            }
+            Self::from_inline(s)
+        } else {
+            Self::from_heap(HEAP::from_parts(len, parts.iter().map(AsRef::as_ref)))
+        }
+    }
+
+    /// Joins the strings from `iter` with `sep` between each. An empty iterator returns an empty
+    /// string, a single string is returned as-is (without copying), and otherwise the result is
+    /// inlined when possible or created with a single heap allocation of the exact length. To
+    /// determine that length, the strings are first collected into a temporary [Vec].
+    /// ```
+    /// use flexstr::{shared_str, SharedStr};
+    ///
+    /// let parts = ["a", "b", "c"].map(SharedStr::from);
+    /// let s = SharedStr::join_iter(parts, ", ");
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "a, b, c");
+    ///
+    /// let long = SharedStr::from_ref_heap("This is too long to be inlined!!!");
+    /// let s = SharedStr::join_iter([long.clone()], ", ");
+    /// assert_eq!(s.as_ptr(), long.as_ptr());
+    ///
+    /// let s = SharedStr::join_iter([long.clone(), long.clone()], "\n");
+    /// assert!(s.is_heap());
+    /// assert_eq!(s.lines().count(), 2);
+    ///
+    /// assert!(SharedStr::join_iter([], ", ").is_static());
+    /// ```
+    pub fn join_iter(iter: impl IntoIterator<Item = Self>, sep: &str) -> Self
+    where
+        HEAP: HeapStorage,
+    {
+        let mut iter = iter.into_iter();
+        let first = match iter.next() {
+            Some(first) => first,
+            None => return Self::EMPTY,
+        };
+        let rest: Vec<Self> = iter.collect();
+        if rest.is_empty() {
+            return first;
+        }
+
+        let len = rest.iter().map(|s| sep.len() + s.len()).sum::<usize>() + first.len();
+        let parts = iter::once(first.as_str()).chain(rest.iter().flat_map(|s| [sep, s.as_str()]));
+
+        if len <= SIZE {
+            let mut s = InlineFlexStr::try_new("").expect("Empty string always fits");
+            for part in parts {
+                s.try_push_str(part).expect("Length checked to fit inline");
+            }
+            Self::from_inline(s)
+        } else {
+            Self::from_heap(HEAP::from_parts(len, parts))
+        }
+    }
+
+    /// Creates a new [FlexStr] consisting of `c` repeated `n` times. The result is inlined when it
+    /// fits, otherwise it is written directly into a single heap allocation of the exact length.
+    ///
+    /// # Panics
+    /// Panics if the length of the result in bytes overflows a `usize`
+    /// ```
+    /// use flexstr::{LocalStr, SharedStr};
+    ///
+    /// let s = LocalStr::repeat_char('-', 5);
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "-----");
+    ///
+    /// let s = SharedStr::repeat_char('-', 1000);
+    /// assert!(s.is_heap());
+    /// assert_eq!(s, "-".repeat(1000));
+    ///
+    /// assert_eq!(LocalStr::repeat_char('☺', 2), "☺☺");
+    /// assert!(LocalStr::repeat_char('-', 0).is_static());
+    /// ```
+    pub fn repeat_char(c: char, n: usize) -> Self
+    where
+        HEAP: HeapStorage,
+    {
+        let mut encoded = [0; 4];
+        let encoded = &*c.encode_utf8(&mut encoded);
+        let len = encoded
+            .len()
+            .checked_mul(n)
+            .expect("Repeated length overflows usize");
+
+        if len == 0 {
+            Self::EMPTY
+        } else if len <= SIZE {
+            let mut s = InlineFlexStr::try_new("").expect("Empty string always fits");
+            for _ in 0..n {
+                s.try_push(c).expect("Length checked to fit inline");
+            }
+            Self::from_inline(s)
+        } else {
+            Self::from_heap(HEAP::from_parts(len, iter::repeat(encoded).take(n)))
+        }
+    }
+
+    /// Creates a new [FlexStr] of `n` chars where the char at each position `i` is `f(i)`. The
+    /// result is inlined when its length in bytes fits.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_char_fn(5, |i| (b'a' + i as u8) as char);
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "abcde");
+    ///
+    /// // 8 chars, but 24 bytes
+    /// let s = LocalStr::from_char_fn(8, |_| '☺');
+    /// assert!(s.is_heap());
+    /// assert_eq!(s, "☺".repeat(8));
+    ///
+    /// assert!(LocalStr::from_char_fn(0, |_| 'a').is_static());
+    /// ```
+    pub fn from_char_fn(n: usize, mut f: impl FnMut(usize) -> char) -> Self {
+        if n == 0 {
+            return Self::EMPTY;
+        }
+
+        let mut buffer = buffer_new!(SIZE);
+        let mut builder = builder_new!(buffer, n);
+
+        for i in 0..n {
+            builder.char_write(f(i));
+        }
+
+        builder_into!(builder, buffer)
+    }
+
+    /// Returns a lowercase version of this string. If it is already lowercase, a clone is returned
+    /// (preserving the storage type and never allocating), otherwise the result is built as with
+    /// [to_lower](crate::ToCase::to_lower).
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("already lowercase");
+    /// assert!(S.to_lower_cow().is_static());
+    ///
+    /// let s = local_str!("Not Lowercase").to_lower_cow();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "not lowercase");
+    ///
+    /// assert!(local_str!("").to_lower_cow().is_static());
+    /// ```
+    pub fn to_lower_cow(&self) -> Self {
+        if self.chars().all(|ch| is_unchanged(ch, ch.to_lowercase())) {
+            self.clone()
+        } else {
+            <str as ToCase<SIZE, PAD1, PAD2, HEAP>>::to_lower(self)
+        }
+    }
+
+    /// Returns an uppercase version of this string. If it is already uppercase, a clone is returned
+    /// (preserving the storage type and never allocating), otherwise the result is built as with
+    /// [to_upper](crate::ToCase::to_upper).
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("ALREADY UPPERCASE");
+    /// assert!(S.to_upper_cow().is_static());
+    ///
+    /// let s = local_str!("Not Uppercase").to_upper_cow();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "NOT UPPERCASE");
+    ///
+    /// assert_eq!(local_str!("ß").to_upper_cow(), "SS");
+    /// ```
+    pub fn to_upper_cow(&self) -> Self {
+        if self.chars().all(|ch| is_unchanged(ch, ch.to_uppercase())) {
+            self.clone()
+        } else {
+            <str as ToCase<SIZE, PAD1, PAD2, HEAP>>::to_upper(self)
+        }
+    }
+
+    /// Returns a new [FlexStr] limited to `max_chars` chars. If this string is longer, the result is
+    /// its first `max_chars - 1` chars followed by `'…'` (inlined when possible), otherwise a clone
+    /// is returned. Chars are never split, but grapheme clusters may be.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("This string is too long for the column");
+    /// let s = S.truncate_ellipsis(10);
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "This stri…");
+    /// assert_eq!(s.chars().count(), 10);
+    ///
+    /// assert!(local_str!("short").truncate_ellipsis(10).is_static());
+    /// assert_eq!(local_str!("☺☺☺☺").truncate_ellipsis(3), "☺☺…");
+    /// assert!(S.truncate_ellipsis(0).is_empty());
+    /// ```
+    pub fn truncate_ellipsis(&self, max_chars: usize) -> Self {
+        if self.char_indices().nth(max_chars).is_none() {
+            self.clone()
+        } else if max_chars == 0 {
+            Self::EMPTY
+        } else {
+            let (idx, _) = self
+                .char_indices()
+                .nth(max_chars - 1)
+                .expect("String is known to be longer");
+            let front = &self[..idx];
+
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer, front.len() + '…'.len_utf8());
+            builder.str_write(front);
+            builder.char_write('…');
+            builder_into!(builder, buffer)
+        }
+    }
+
+    /// Returns a new [FlexStr] with `fill` prepended until it is `width` chars long. The result is
+    /// inlined when possible. If this string is already at least `width` chars long, a clone is
+    /// returned.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("42");
+    /// let s = S.pad_start(5, '0');
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "00042");
+    ///
+    /// assert_eq!(S.pad_start(4, '☺'), "☺☺42");
+    /// assert!(S.pad_start(1, '0').is_static());
+    /// ```
+    #[inline]
+    pub fn pad_start(&self, width: usize, fill: char) -> Self {
+        self.pad(width, fill, true)
+    }
+
+    /// Returns a new [FlexStr] with `fill` appended until it is `width` chars long. The result is
+    /// inlined when possible. If this string is already at least `width` chars long, a clone is
+    /// returned.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("42");
+    /// let s = S.pad_end(5, '.');
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "42...");
+    ///
+    /// let s = S.pad_end(40, '.');
+    /// assert!(s.is_heap());
+    /// assert!(S.pad_end(2, '.').is_static());
+    /// ```
+    #[inline]
+    pub fn pad_end(&self, width: usize, fill: char) -> Self {
+        self.pad(width, fill, false)
+    }
+
+    /// Returns a new [FlexStr] containing only the chars for which `pred` returns true. The result
+    /// is inlined when possible. If every char is kept a clone is returned, and if none are the
+    /// result is empty.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("strip all of  the spaces ");
+    /// let s = S.filter_chars(|ch| !ch.is_whitespace());
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "stripallofthespaces");
+    ///
+    /// assert!(S.filter_chars(|_| true).is_static());
+    /// assert!(S.filter_chars(|_| false).is_empty());
+    /// ```
+    pub fn filter_chars(&self, mut pred: impl FnMut(char) -> bool) -> Self {
+        match self.char_indices().find(|&(_, ch)| !pred(ch)) {
+            Some((idx, ch)) => {
+                let mut buffer = buffer_new!(SIZE);
+                let mut builder = builder_new!(buffer, self.len() - ch.len_utf8());
+
+                builder.str_write(&self[..idx]);
+                for ch in self[idx + ch.len_utf8()..].chars().filter(|&ch| pred(ch)) {
+                    builder.char_write(ch);
+                }
+
+                builder_into!(builder, buffer)
+            }
+            None => self.clone(),
+        }
+    }
+
+    /// Returns a new [FlexStr] with leading and trailing whitespace removed and every internal run of
+    /// whitespace replaced with a single ASCII space. The result is inlined when possible. If the
+    /// string is already normalized a clone is returned.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("  a \t b \n c  ");
+    /// let s = S.normalize_whitespace();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "a b c");
+    ///
+    /// assert!(local_str!("a b c").normalize_whitespace().is_static());
+    /// assert!(local_str!(" \t\n ").normalize_whitespace().is_static());
+    /// ```
+    pub fn normalize_whitespace(&self) -> Self {
+        let mut prev_space = true;
+        let is_normalized = self.chars().all(|ch| {
+            let ok = !ch.is_whitespace() || (ch == ' ' && !prev_space);
+            prev_space = ch.is_whitespace();
+            ok
+        }) && !prev_space;
+
+        if is_normalized || self.is_empty() {
+            self.clone()
+        } else if self.trim_start().is_empty() {
+            Self::EMPTY
+        } else {
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer, self.len());
+
+            for (idx, word) in str::split_whitespace(self).enumerate() {
+                if idx > 0 {
+                    builder.char_write(' ');
+                }
+                builder.str_write(word);
+            }
+
+            builder_into!(builder, buffer)
+        }
+    }
+
+    /// Returns a new [FlexStr] with the chars of this string in reverse order. The result is
+    /// inlined when possible.
+    ///
+    /// # Note
+    /// This reverses by [char] (Unicode scalar value), so the result is always valid UTF-8, but
+    /// grapheme clusters made up of multiple chars (such as a letter followed by a combining accent)
+    /// are not kept together.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("abc");
+    /// let s = S.reversed();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "cba");
+    ///
+    /// assert_eq!(local_str!("a☺ü").reversed(), "ü☺a");
+    /// assert_eq!(local_str!("x").reversed(), "x");
+    /// assert!(local_str!("").reversed().is_empty());
+    /// ```
+    pub fn reversed(&self) -> Self {
+        let mut buffer = buffer_new!(SIZE);
+        let mut builder = builder_new!(buffer, self.len());
+
+        for ch in self.chars().rev() {
+            builder.char_write(ch);
+        }
+
+        builder_into!(builder, buffer)
+    }
+
+    /// Returns a new [FlexStr] with every non-ASCII char replaced by `replacement`. If this string
+    /// is already all ASCII, a clone is returned (preserving the storage type), otherwise the result
+    /// is inlined when possible.
+    ///
+    /// # Panics
+    /// Panics if `replacement` is not ASCII
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("already ascii");
+    /// assert!(S.to_ascii_only('?').is_static());
+    ///
+    /// let s = local_str!("naïve ☺").to_ascii_only('?');
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "na?ve ?");
+    /// ```
+    ///
+    /// ```should_panic
+    /// use flexstr::local_str;
+    ///
+    /// local_str!("naïve").to_ascii_only('☺');
+    /// ```
+    pub fn to_ascii_only(&self, replacement: char) -> Self {
+        assert!(replacement.is_ascii(), "Replacement char must be ASCII");
+
+        if self.is_ascii() {
+            self.clone()
+        } else {
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer, self.len());
+
+            for ch in self.chars() {
+                builder.char_write(if ch.is_ascii() { ch } else { replacement });
+            }
+
+            builder_into!(builder, buffer)
+        }
+    }
+
+    /// Returns a new [FlexStr] with each char escaped using [char::escape_default]. The result is
+    /// inlined when possible. Use [str::escape_default] (via [Deref]) for a lazy iterator instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a\tb\n");
+    /// let s = S.escape_default_flex();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "a\\tb\\n");
+    /// assert_eq!(local_str!("plain").escape_default_flex(), "plain");
+    /// assert_eq!(s, S.escape_default().collect::<String>());
+    /// ```
+    #[inline]
+    pub fn escape_default_flex(&self) -> Self {
+        self.escape(str::escape_default)
+    }
+
+    /// Returns a new [FlexStr] with each char escaped using [char::escape_debug]. The result is
+    /// inlined when possible. Use [str::escape_debug] (via [Deref]) for a lazy iterator instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a\tb\n☺");
+    /// let s = S.escape_debug_flex();
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "a\\tb\\n☺");
+    /// ```
+    #[inline]
+    pub fn escape_debug_flex(&self) -> Self {
+        self.escape(str::escape_debug)
+    }
+
+    /// Applies `f` to this string and returns the result as a new [FlexStr], inlined when possible.
+    /// This is a catch-all for transformations not provided natively.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("HELLO");
+    /// let s = S.map_str(|s| s.to_lowercase());
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "hello");
+    ///
+    /// let s = S.map_str(|s| s.repeat(10));
+    /// assert!(s.is_heap());
+    /// assert_eq!(s.len(), 50);
+    /// ```
+    #[inline]
+    pub fn map_str(&self, f: impl FnOnce(&str) -> String) -> Self {
+        Self::from_ref(f(self))
+    }
+
+    /// Applies `f` to each line (as split by [str::lines]) and joins the results with `\n`,
+    /// returning a new [FlexStr] that is inlined when possible. A trailing line ending is kept as a
+    /// trailing `\n`, while `\r\n` line endings are normalized to `\n`.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a\nb\r\n\nc\n");
+    /// let s = S.map_lines(|line| format!("  {line}"));
+    /// assert!(s.is_inline());
+    /// assert_eq!(s, "  a\n  b\n  \n  c\n");
+    ///
+    /// assert_eq!(local_str!("a\nb").map_lines(str::to_uppercase), "A\nB");
+    /// assert!(local_str!("").map_lines(|line| format!("  {line}")).is_empty());
+    /// ```
+    pub fn map_lines(&self, mut f: impl FnMut(&str) -> String) -> Self {
+        let mut buffer = buffer_new!(SIZE);
+        let mut builder = builder_new!(buffer, self.len());
+
+        for (idx, line) in str::lines(self).enumerate() {
+            if idx > 0 {
+                builder.char_write('\n');
+            }
+            builder.str_write(f(line));
+        }
+        if str::ends_with(self, '\n') {
+            builder.char_write('\n');
+        }
+
+        builder_into!(builder, buffer)
+    }
+
+    fn escape<'a, F, I>(&'a self, f: F) -> Self
+    where
+        F: FnOnce(&'a str) -> I,
+        I: Iterator<Item = char>,
+    {
+        let mut buffer = buffer_new!(SIZE);
+        let mut builder = builder_new!(buffer, self.len());
+
+        for ch in f(self) {
+            builder.char_write(ch);
+        }
+
+        builder_into!(builder, buffer)
+    }
+
+    fn pad(&self, width: usize, fill: char, start: bool) -> Self {
+        let char_count = self.chars().count();
+
+        if char_count >= width {
+            self.clone()
+        } else {
+            let fill_count = width - char_count;
+
+            let mut buffer = buffer_new!(SIZE);
+            let mut builder = builder_new!(buffer, self.len() + fill_count * fill.len_utf8());
+
+            if !start {
+                builder.str_write(self);
+            }
+            for _ in 0..fill_count {
+                builder.char_write(fill);
+            }
+            if start {
+                builder.str_write(self);
+            }
+
+            builder_into!(builder, buffer)
+        }
+    }
+}
+
+// Returns true if the case mapping of `ch` is exactly `ch` itself
+#[inline]
+fn is_unchanged(ch: char, mut mapped: impl Iterator<Item = char>) -> bool {
+    mapped.next() == Some(ch) && mapped.next().is_none()
+}
+
+// *** In-place Mutation ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: HeapStorage,
+{
+    /// Attempts to convert this string to uppercase in place. This only succeeds if the contents
+    /// are uniquely owned (inlined, or heap storage with no other references) and every char's
+    /// uppercase form is a single char of the same UTF-8 length, so the length doesn't change.
+    /// Otherwise false is returned and the string is left unchanged.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// let mut s = LocalStr::from_ref("привет, κόσμε");
+    /// assert!(s.try_to_uppercase_in_place());
+    /// assert_eq!(s, "ПРИВЕТ, ΚΌΣΜΕ");
+    ///
+    /// // 'ß' uppercases to "SS"
+    /// let mut s = LocalStr::from_ref("straße");
+    /// assert!(!s.try_to_uppercase_in_place());
+    /// assert_eq!(s, "straße");
+    ///
+    /// // Static and shared strings can't be mutated
+    /// let mut s = local_str!("static");
+    /// assert!(!s.try_to_uppercase_in_place());
+    ///
+    /// let mut s = LocalStr::from_ref("This is too long to be inlined");
+    /// let s2 = s.clone();
+    /// assert!(!s.try_to_uppercase_in_place());
+    /// drop(s2);
+    /// assert!(s.try_to_uppercase_in_place());
+    /// assert_eq!(s, "THIS IS TOO LONG TO BE INLINED");
+    /// ```
+    pub fn try_to_uppercase_in_place(&mut self) -> bool {
+        // Check everything up front so we never partially mutate
+        let same_len = |ch: char| {
+            let mut upper = ch.to_uppercase();
+            matches!((upper.next(), upper.next()), (Some(u), None) if u.len_utf8() == ch.len_utf8())
+        };
+        if !self.chars().all(same_len) {
+            return false;
+        }
+
+        match self.make_mut() {
+            Some(s) => {
+                let mut idx = 0;
+
+                while let Some(ch) = s[idx..].chars().next() {
+                    let upper = ch.to_uppercase().next().expect("Checked above");
+                    // SAFETY: The uppercase char has the same UTF-8 length (checked above) so it
+                    // exactly overwrites the original char and the contents remain valid UTF-8
+                    unsafe {
+                        upper.encode_utf8(&mut s.as_bytes_mut()[idx..idx + ch.len_utf8()]);
+                    }
+                    idx += ch.len_utf8();
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// *** Iterators ***
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    /// Returns an iterator over the non-overlapping matches of `pat` in this string along with the
+    /// byte offset of each match (as defined by [str::match_indices]). Each match is returned as a
+    /// new [FlexStr] (wrapped literal or inlined when possible). See [FindPattern] for the types of
+    /// patterns accepted. Use [str::match_indices] (via [Deref]) to borrow each match as a `&str`
+    /// instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a,b,c");
+    /// let matches: Vec<(usize, LocalStr)> = S.match_indices_flex(',').collect();
+    /// assert_eq!(matches, vec![(1, local_str!(",")), (3, local_str!(","))]);
+    /// assert!(matches[0].1.is_static());
+    ///
+    /// assert_eq!(S.match_indices_flex(';').count(), 0);
+    ///
+    /// const T: LocalStr = local_str!("abXabab☺");
+    /// let idxs: Vec<usize> = T.match_indices_flex("ab").map(|(idx, _)| idx).collect();
+    /// assert_eq!(idxs, vec![0, 3, 5]);
+    /// let idxs: Vec<usize> = T.match_indices_flex("").map(|(idx, _)| idx).collect();
+    /// assert_eq!(idxs, T.as_str().match_indices("").map(|(idx, _)| idx).collect::<Vec<_>>());
+    /// let matches: Vec<(usize, LocalStr)> = T.match_indices_flex(char::is_uppercase).collect();
+    /// assert_eq!(matches, vec![(2, local_str!("X"))]);
+    ///
+    /// let s: LocalStr = "a,b".into();
+    /// let borrowed: Vec<(usize, &str)> = s.match_indices(',').collect();
+    /// assert_eq!(borrowed, vec![(1, ",")]);
+    /// ```
+    #[inline]
+    pub fn match_indices_flex<'a, P>(&'a self, pat: P) -> impl Iterator<Item = (usize, Self)> + 'a
+    where
+        P: FindPattern + 'a,
+    {
+        match_ranges(self, pat).map(move |(start, end)| (start, self.sub_flex(&self[start..end])))
+    }
+
+    /// Returns an iterator over the parts of this string separated by `pat` (as defined by
+    /// [str::split_terminator]). Unlike `split`, a trailing empty part after a final `pat` is
+    /// skipped. Each part is returned as a new [FlexStr] (wrapped literal or inlined when possible).
+    /// See [FindPattern] for the types of patterns accepted. Use [str::split_terminator] (via
+    /// [Deref]) to borrow each part as a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a.b.");
+    /// let parts: Vec<LocalStr> = S.split_terminator_flex('.').collect();
+    /// assert_eq!(parts, S.as_str().split_terminator('.').collect::<Vec<_>>());
+    /// assert_eq!(parts, vec!["a", "b"]);
+    /// assert!(parts.iter().all(LocalStr::is_static));
+    ///
+    /// assert_eq!(local_str!(".a..b").split_terminator_flex('.').collect::<Vec<_>>(), vec!["", "a", "", "b"]);
+    /// assert_eq!(local_str!("a\r\nb\r\n").split_terminator_flex("\r\n").collect::<Vec<_>>(), vec!["a", "b"]);
+    /// assert_eq!(local_str!("ab").split_terminator_flex("").collect::<Vec<_>>(), vec!["", "a", "b"]);
+    /// assert_eq!(local_str!("").split_terminator_flex('.').count(), 0);
+    ///
+    /// let s: LocalStr = "a.b.".into();
+    /// let borrowed: Vec<&str> = s.split_terminator('.').collect();
+    /// assert_eq!(borrowed, vec!["a", "b"]);
+    /// ```
+    #[inline]
+    pub fn split_terminator_flex<'a, P>(&'a self, pat: P) -> impl Iterator<Item = Self> + 'a
+    where
+        P: FindPattern + 'a,
+    {
+        let mut matches = match_ranges(self, pat);
+        let mut pos = Some(0);
+
+        iter::from_fn(move || {
+            let from = pos.take()?;
+            match matches.next() {
+                Some((start, end)) => {
+                    pos = Some(end);
+                    Some(self.sub_flex(&self[from..start]))
+                }
+                // The empty part after a final match is skipped
+                None if from == self.len() => None,
+                None => Some(self.sub_flex(&self[from..])),
+            }
+        })
+    }
+
+    /// Returns an iterator over the lines of this string, each including its line ending (`\n` or
+    /// `\r\n`). A final line without a line ending is returned as-is, and an empty string yields no
+    /// lines. Each line is returned as a new [FlexStr] (wrapped literal or inlined when possible).
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a\nb\r\n\nc");
+    /// let lines: Vec<LocalStr> = S.split_inclusive_lines().collect();
+    /// assert_eq!(lines, S.as_str().split_inclusive('\n').collect::<Vec<_>>());
+    /// assert_eq!(lines, vec!["a\n", "b\r\n", "\n", "c"]);
+    /// assert!(lines.iter().all(LocalStr::is_static));
+    ///
+    /// assert_eq!(LocalStr::concat(&lines), S);
+    /// assert_eq!(local_str!("").split_inclusive_lines().count(), 0);
+    /// ```
+    #[inline]
+    pub fn split_inclusive_lines(&self) -> impl Iterator<Item = Self> + '_ {
+        // A `\r` directly before the `\n` is part of the line, so `\r\n` endings are kept whole
+        str::split_inclusive(self, '\n').map(move |s| self.sub_flex(s))
+    }
+
+    /// Returns an iterator over the parts of this string separated by `pat` in reverse order (as
+    /// defined by [str::rsplit]). Each part is returned as a new [FlexStr] (wrapped literal or
+    /// inlined when possible). See [FindPattern] for the types of patterns accepted. Use
+    /// [str::rsplit] (via [Deref]) to borrow each part as a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("a.b.");
+    /// let parts: Vec<LocalStr> = S.rsplit_flex('.').collect();
+    /// assert_eq!(parts, S.as_str().rsplit('.').collect::<Vec<_>>());
+    /// assert_eq!(parts, vec!["", "b", "a"]);
+    ///
+    /// assert_eq!(local_str!("none").rsplit_flex('.').collect::<Vec<_>>(), vec!["none"]);
+    /// assert_eq!(local_str!("a::b::c").rsplit_flex("::").collect::<Vec<_>>(), vec!["c", "b", "a"]);
+    /// assert_eq!(local_str!("ab").rsplit_flex("").collect::<Vec<_>>(), vec!["", "b", "a", ""]);
+    /// assert_eq!(local_str!("").rsplit_flex('.').collect::<Vec<_>>(), vec![""]);
+    ///
+    /// let s: LocalStr = "a::b".into();
+    /// let borrowed: Vec<&str> = s.rsplit("::").collect();
+    /// assert_eq!(borrowed, vec!["b", "a"]);
+    /// ```
+    #[inline]
+    pub fn rsplit_flex<'a, P>(&'a self, pat: P) -> impl Iterator<Item = Self> + 'a
+    where
+        P: FindPattern + 'a,
+    {
+        let mut matches = rmatch_ranges(self, pat);
+        let mut pos = Some(self.len());
+
+        iter::from_fn(move || {
+            let to = pos.take()?;
+            match matches.next() {
+                Some((start, end)) => {
+                    pos = Some(start);
+                    Some(self.sub_flex(&self[end..to]))
+                }
+                None => Some(self.sub_flex(&self[..to])),
+            }
+        })
+    }
+
+    /// Returns an iterator over the whitespace separated words of this string (as defined by
+    /// [str::split_whitespace]). Each word is returned as a new [FlexStr] (wrapped literal or
+    /// inlined when possible). Use [str::split_whitespace] (via [Deref]) to borrow each word as a
+    /// `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("  a  quick\tfox\n");
+    /// let words: Vec<LocalStr> = S.split_whitespace_flex().collect();
+    /// assert_eq!(words, S.as_str().split_whitespace().collect::<Vec<_>>());
+    /// assert!(words.iter().all(LocalStr::is_static));
+    ///
+    /// assert_eq!(local_str!(" \t\n ").split_whitespace_flex().count(), 0);
+    /// ```
+    #[inline]
+    pub fn split_whitespace_flex(&self) -> impl Iterator<Item = Self> + '_ {
+        str::split_whitespace(self).map(move |s| self.sub_flex(s))
+    }
+
+    /// Returns an iterator over the ASCII whitespace separated words of this string (as defined by
+    /// [str::split_ascii_whitespace]). Each word is returned as a new [FlexStr] (wrapped literal or
+    /// inlined when possible). Use [str::split_ascii_whitespace] (via [Deref]) to borrow each word
+    /// as a `&str` instead.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// const S: LocalStr = local_str!("  a\u{A0}quick\tfox ");
+    /// let words: Vec<LocalStr> = S.split_ascii_whitespace_flex().collect();
+    /// assert_eq!(words, S.as_str().split_ascii_whitespace().collect::<Vec<_>>());
+    /// assert_eq!(words, vec!["a\u{A0}quick", "fox"]);
+    ///
+    /// let word = LocalStr::from_ref("word");
+    /// assert_eq!(word.split_ascii_whitespace_flex().collect::<Vec<_>>(), vec![word.clone()]);
+    /// ```
+    #[inline]
+    pub fn split_ascii_whitespace_flex(&self) -> impl Iterator<Item = Self> + '_ {
+        str::split_ascii_whitespace(self).map(move |s| self.sub_flex(s))
+    }
+}
+
+// Returns an iterator over the start and end byte indices of the non-overlapping matches of `pat`
+// in `s`, with the same semantics as `str::match_indices`
+fn match_ranges<'a, P>(s: &'a str, mut pat: P) -> impl Iterator<Item = (usize, usize)> + 'a
+where
+    P: FindPattern + 'a,
+{
+    let mut pos = Some(0);
+
+    iter::from_fn(move || {
+        let from = pos.take()?;
+        let (start, end) = pat.match_in(&s[from..])?;
+        let (start, end) = (from + start, from + end);
+
+        // An empty match would be found again at the same position, so resume after the next char
+        pos = if start == end {
+            s[end..].chars().next().map(|ch| end + ch.len_utf8())
+        } else {
+            Some(end)
+        };
+        Some((start, end))
+    })
+}
+
+// Returns an iterator over the start and end byte indices of the non-overlapping matches of `pat`
+// in `s` in reverse order, with the same semantics as `str::rmatch_indices`
+fn rmatch_ranges<'a, P>(s: &'a str, mut pat: P) -> impl Iterator<Item = (usize, usize)> + 'a
+where
+    P: FindPattern + 'a,
+{
+    let mut pos = Some(s.len());
+
+    iter::from_fn(move || {
+        let to = pos.take()?;
+        let (start, end) = pat.rmatch_in(&s[..to])?;
+
+        // An empty match would be found again at the same position, so resume before the prior char
+        pos = if start == end {
+            s[..start]
+                .chars()
+                .next_back()
+                .map(|ch| start - ch.len_utf8())
+        } else {
+            Some(start)
+        };
+        Some((start, end))
+    })
+}
+
+// *** Chars Into Iterator ***
+
+/// An owning iterator over the chars of a [FlexStr]. This is returned from the
+/// [IntoIterator] implementation of [FlexStr] and keeps the string (and any heap storage) alive
+/// until the iterator is dropped.
+pub struct CharsIntoIter<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> {
+    s: FlexStr<SIZE, PAD1, PAD2, HEAP>,
+    front: usize,
+    back: usize,
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
+    CharsIntoIter<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    #[inline]
+    pub(crate) fn new(s: FlexStr<SIZE, PAD1, PAD2, HEAP>) -> Self {
+        let back = s.len();
+        Self { s, front: 0, back }
+    }
+
+    /// Returns the part of the string that has not yet been iterated over
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.s[self.front..self.back]
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Iterator
+    for CharsIntoIter<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let ch = self.as_str().chars().next()?;
+        self.front += ch.len_utf8();
+        Some(ch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_str().chars().size_hint()
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> DoubleEndedIterator
+    for CharsIntoIter<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let ch = self.as_str().chars().next_back()?;
+        self.back -= ch.len_utf8();
+        Some(ch)
+    }
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FusedIterator
+    for CharsIntoIter<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> Debug
+    for CharsIntoIter<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CharsIntoIter")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    #[test]
+    fn concat_single_allocation() {
+        use crate::counting_alloc::count_allocations;
+        use crate::{BoxedStr, LocalStr, SharedStr};
+        use alloc::vec::Vec;
+
+        let parts: Vec<LocalStr> = (0..100).map(|_| "0123456789".into()).collect();
+        let expected: Vec<&str> = parts.iter().map(|part| part.as_str()).collect();
+        let expected = expected.concat();
+
+        assert_eq!(
+            count_allocations(|| assert_eq!(SharedStr::concat(&parts), expected)),
+            1
+        );
+        assert_eq!(
+            count_allocations(|| assert_eq!(LocalStr::concat(&parts), expected)),
+            1
+        );
+        assert_eq!(
+            count_allocations(|| assert_eq!(BoxedStr::concat(&parts), expected)),
+            1
+        );
+        assert_eq!(
+            count_allocations(|| assert!(LocalStr::concat(&parts[..2]).is_inline())),
+            0
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn join_iter_single_result_allocation() {
+        use crate::counting_alloc::count_allocations;
+        use crate::SharedStr;
+        use alloc::vec::Vec;
+
+        let parts: Vec<SharedStr> = (0..100)
+            .map(|_| SharedStr::from_ref_heap("This is too long to be inlined!!!"))
+            .collect();
+        let expected: Vec<&str> = parts.iter().map(|part| part.as_str()).collect();
+        let expected = expected.join(", ");
+
+        // One allocation for the temporary `Vec` and exactly one for the result
+        let iter = parts.iter().cloned();
+        assert_eq!(
+            count_allocations(|| assert_eq!(SharedStr::join_iter(iter, ", "), expected)),
+            2
+        );
+        // A single part is returned without copying
+        let iter = parts[..1].iter().cloned();
+        assert_eq!(
+            count_allocations(|| assert!(SharedStr::join_iter(iter, ", ").is_heap())),
+            0
+        );
+    }
+}
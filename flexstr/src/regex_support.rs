@@ -0,0 +1,44 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use regex::Regex;
+
+use crate::FlexStr;
+
+/// Matches `re` against `haystack` returning each capture group (starting with group 0, the whole
+/// match) as a new [FlexStr], or `None` if there is no match. Captures of a wrapped string literal
+/// are also wrapped literals (no copy), otherwise they are inlined when possible.
+///
+/// Optional groups that did not participate in the match are returned as empty strings so that
+/// each group is always at the index of its group number.
+/// ```
+/// use flexstr::{captures_flex, local_str, LocalStr};
+/// use regex::Regex;
+///
+/// const S: LocalStr = local_str!("key=value");
+/// let re = Regex::new(r"(\w+)=(\w+)(;)?").unwrap();
+///
+/// let caps = captures_flex(&re, &S).unwrap();
+/// assert_eq!(caps, vec!["key=value", "key", "value", ""]);
+/// assert!(caps[1].is_static() && caps[2].is_static());
+///
+/// assert!(captures_flex(&re, &local_str!("no match")).is_none());
+/// ```
+pub fn captures_flex<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>(
+    re: &Regex,
+    haystack: &FlexStr<SIZE, PAD1, PAD2, HEAP>,
+) -> Option<Vec<FlexStr<SIZE, PAD1, PAD2, HEAP>>>
+where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    let caps = re.captures(haystack)?;
+
+    Some(
+        caps.iter()
+            .map(|m| match m {
+                Some(m) => haystack.sub_flex(m.as_str()),
+                None => FlexStr::EMPTY,
+            })
+            .collect(),
+    )
+}
@@ -3,7 +3,7 @@ use core::fmt::{Debug, Formatter};
 use core::ops::Deref;
 use core::{fmt, mem, ptr, str};
 
-use crate::storage::StorageType;
+use crate::storage::{InvalidInlineArray, StorageType, TooLongForInlining};
 
 /// Using this inline capacity will result in a type with the same memory size as a builtin [String]
 pub const STRING_SIZED_INLINE: usize = mem::size_of::<String>() - 2;
@@ -62,6 +62,44 @@ impl<const N: usize> InlineFlexStr<N> {
         }
     }
 
+    /// Attempts to create a new `InlineFlexStr` from the first `len` bytes of `data`. An error is
+    /// returned if `len` exceeds the inline capacity or those bytes are not valid UTF-8.
+    /// ```
+    /// use flexstr::storage::inline::InlineFlexStr;
+    /// use flexstr::{InvalidInlineArray, STRING_SIZED_INLINE};
+    ///
+    /// let mut data = [0; STRING_SIZED_INLINE];
+    /// data[..5].copy_from_slice(b"hello");
+    /// let s = InlineFlexStr::try_from_array(data, 5).unwrap();
+    /// assert_eq!(&*s, "hello");
+    ///
+    /// assert!(matches!(
+    ///     InlineFlexStr::try_from_array(data, STRING_SIZED_INLINE as u8 + 1),
+    ///     Err(InvalidInlineArray::TooLong(_))
+    /// ));
+    /// data[0] = 0xFF;
+    /// assert!(matches!(
+    ///     InlineFlexStr::try_from_array(data, 5),
+    ///     Err(InvalidInlineArray::InvalidUtf8(_))
+    /// ));
+    /// ```
+    pub fn try_from_array(data: [u8; N], len: u8) -> Result<Self, InvalidInlineArray> {
+        let len_usize = len as usize;
+
+        if len_usize > Self::capacity() {
+            Err(InvalidInlineArray::TooLong(TooLongForInlining {
+                length: len_usize,
+                inline_capacity: Self::capacity(),
+            }))
+        } else {
+            match str::from_utf8(&data[..len_usize]) {
+                // SAFETY: We just validated the length and contents
+                Ok(s) => unsafe { Ok(Self::new(s)) },
+                Err(err) => Err(InvalidInlineArray::InvalidUtf8(err)),
+            }
+        }
+    }
+
     /// Returns the capacity of this inline string
     #[inline]
     pub fn capacity() -> usize {
@@ -99,6 +137,71 @@ impl<const N: usize> InlineFlexStr<N> {
             false
         }
     }
+
+    /// Appends `s` to the end of this string in place if the result fits within the inline
+    /// capacity. If it does not, an error is returned and the string is left unchanged.
+    /// ```
+    /// use flexstr::storage::inline::InlineFlexStr;
+    ///
+    /// let mut s = <InlineFlexStr>::try_new("Hello").unwrap();
+    /// s.try_push_str(", world").unwrap();
+    /// assert_eq!(&*s, "Hello, world");
+    ///
+    /// let err = s.try_push_str("! This will not fit").unwrap_err();
+    /// assert_eq!(err.inline_capacity, <InlineFlexStr>::capacity());
+    /// assert_eq!(&*s, "Hello, world");
+    /// ```
+    #[inline]
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), TooLongForInlining> {
+        if self.try_concat(s) {
+            Ok(())
+        } else {
+            Err(TooLongForInlining {
+                length: self.len() + s.len(),
+                inline_capacity: Self::capacity(),
+            })
+        }
+    }
+
+    /// Appends the char `c` to the end of this string in place if the result fits within the inline
+    /// capacity. If it does not, an error is returned and the string is left unchanged.
+    /// ```
+    /// use flexstr::storage::inline::InlineFlexStr;
+    ///
+    /// let mut s = <InlineFlexStr>::try_new("").unwrap();
+    /// s.try_push('a').unwrap();
+    /// s.try_push('☺').unwrap();
+    /// assert_eq!(&*s, "a☺");
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, c: char) -> Result<(), TooLongForInlining> {
+        self.try_push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends `s` to the end of this string in place
+    ///
+    /// # Panics
+    /// Panics if the result would not fit within the inline capacity. See
+    /// [try_push_str](Self::try_push_str) for a non-panicking version.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        if let Err(err) = self.try_push_str(s) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Returns a mutable reference to the contents of this string
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        let data = &mut self.data[..self.len as usize];
+
+        unsafe {
+            // SAFETY: The contents are always obtained from a valid UTF8 str, so they must be valid
+            // Additionally, we clamp the size of the slice passed to be no longer than our str length
+            let data = &mut *(data as *mut [mem::MaybeUninit<u8>] as *mut [u8]);
+            str::from_utf8_unchecked_mut(data)
+        }
+    }
 }
 
 impl<const N: usize> Debug for InlineFlexStr<N> {
@@ -136,6 +239,19 @@ impl<const N: usize> Deref for InlineFlexStr<N> {
     }
 }
 
+impl<const N: usize> fmt::Write for InlineFlexStr<N> {
+    /// Appends `s` in place. If it does not fit within the inline capacity, nothing is written and
+    /// an error is returned.
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.try_concat(s) {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
 impl<const N: usize> TryFrom<String> for InlineFlexStr<N> {
     type Error = String;
 
@@ -209,4 +325,72 @@ mod tests {
         assert!(!s.try_concat(lit2));
         assert_eq!(&*s, lit);
     }
+
+    #[test]
+    fn from_array_full() {
+        let cap = <InlineFlexStr>::capacity();
+        let mut data = [b'x'; crate::STRING_SIZED_INLINE];
+        data[cap - 3..].copy_from_slice("☺".as_bytes());
+        let s = <InlineFlexStr>::try_from_array(data, cap as u8).expect("bad array");
+        assert_eq!(s.len(), cap);
+        assert!(s.ends_with('☺'));
+
+        // Truncating in the middle of a multi-byte char is invalid
+        assert!(<InlineFlexStr>::try_from_array(data, cap as u8 - 1).is_err());
+    }
+
+    #[test]
+    fn write_overflow() {
+        use core::fmt::Write;
+
+        let mut s = <InlineFlexStr>::try_new("").expect("bad inline str");
+        write!(s, "{}-{}", 1, 2).expect("should fit");
+        assert_eq!(&*s, "1-2");
+
+        let lit = "This is way too long to be an inline string!!!";
+        assert!(write!(s, "{}", lit).is_err());
+        // Each `write_str` call is all or nothing
+        assert_eq!(&*s, "1-2");
+    }
+
+    #[test]
+    fn push_str_exact_fit() {
+        let mut s = <InlineFlexStr>::try_new("").expect("bad inline str");
+        let lit = "x".repeat(<InlineFlexStr>::capacity());
+        let (front, back) = lit.split_at(10);
+        s.try_push_str(front).expect("front should fit");
+        s.try_push_str(back).expect("back should exactly fit");
+        assert_eq!(&*s, lit);
+        assert_eq!(s.len(), <InlineFlexStr>::capacity());
+    }
+
+    #[test]
+    fn push_at_capacity_boundary() {
+        let cap = <InlineFlexStr>::capacity();
+        let lit = "x".repeat(cap - 3);
+        let mut s = <InlineFlexStr>::try_new(&lit).expect("bad inline str");
+
+        // A 4-byte char doesn't fit in the 3 remaining bytes and must not be partially written
+        let err = s.try_push('😀').unwrap_err();
+        assert_eq!(err.length, cap + 1);
+        assert_eq!(&*s, lit);
+
+        // ...but a 3-byte char fits exactly
+        s.try_push('☺').expect("should exactly fit");
+        assert_eq!(s.len(), cap);
+        assert!(s.try_push('x').is_err());
+        assert_eq!(&*s, lit + "☺");
+    }
+
+    #[test]
+    fn push_str_overflow() {
+        let lit = "Inline";
+        let mut s = <InlineFlexStr>::try_new(lit).expect("bad inline str");
+        let err = s
+            .try_push_str(" way too long to be an inline string!!!")
+            .unwrap_err();
+        assert_eq!(err.length, 45);
+        assert_eq!(err.inline_capacity, <InlineFlexStr>::capacity());
+        assert_eq!(&*s, lit);
+    }
 }
@@ -2,8 +2,15 @@ pub(crate) mod heap;
 pub mod inline;
 pub(crate) mod static_ref;
 
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
 use core::fmt;
 use core::fmt::{Debug, Display, Formatter};
+use core::iter;
+use core::ops::Deref;
+use core::str::Utf8Error;
 
 // *** Wrong Storage Type ***
 
@@ -30,10 +37,66 @@ impl Display for WrongStorageType {
 #[cfg(feature = "std")]
 impl std::error::Error for WrongStorageType {}
 
+// *** Too Long For Inlining ***
+
+/// Error type returned from [try_push_str](crate::storage::inline::InlineFlexStr::try_push_str)
+/// when the appended string would not fit within the inline capacity
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TooLongForInlining {
+    /// The length in bytes the string would have had after the operation
+    pub length: usize,
+    /// The inline capacity in bytes
+    pub inline_capacity: usize,
+}
+
+impl Display for TooLongForInlining {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The string length ({}) exceeds the inline capacity ({})",
+            self.length, self.inline_capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooLongForInlining {}
+
+// *** Invalid Inline Array ***
+
+/// Error type returned from [try_from_array](crate::storage::inline::InlineFlexStr::try_from_array)
+/// when the array and length given do not form a valid inline string
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InvalidInlineArray {
+    /// The length given exceeds the inline capacity
+    TooLong(TooLongForInlining),
+    /// The bytes given are not valid UTF-8
+    InvalidUtf8(Utf8Error),
+}
+
+impl Display for InvalidInlineArray {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidInlineArray::TooLong(err) => Display::fmt(err, f),
+            InvalidInlineArray::InvalidUtf8(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidInlineArray {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InvalidInlineArray::TooLong(err) => Some(err),
+            InvalidInlineArray::InvalidUtf8(err) => Some(err),
+        }
+    }
+}
+
 // *** Storage Type ***
 
 /// Represents the storage type used by a particular [FlexStr](crate::FlexStr)
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum StorageType {
     /// Denotes that this [FlexStr](crate::FlexStr) is a wrapper string literal
@@ -43,3 +106,100 @@ pub enum StorageType {
     /// Denotes that this [FlexStr](crate::FlexStr) uses heap-based storage
     Heap,
 }
+
+// *** Heap Storage ***
+
+/// Operations on the heap storage types used by [FlexStr](crate::FlexStr). This is implemented for
+/// [`Rc<str>`], [`Arc<str>`] and [`Box<str>`].
+pub trait HeapStorage: Deref<Target = str> + for<'a> From<&'a str> + Sized {
+    /// Creates a new heap string from the concatenation of `parts` using a single allocation of
+    /// exactly `len` bytes.
+    ///
+    /// # Panics
+    /// Panics if the lengths of `parts` do not sum to `len`
+    fn from_parts<'a>(len: usize, parts: impl IntoIterator<Item = &'a str>) -> Self;
+
+    /// Returns a mutable reference to the string if it is uniquely owned, otherwise `None`
+    fn get_mut(&mut self) -> Option<&mut str>;
+
+    /// Returns the number of strong references to this heap string (always 1 if not reference
+    /// counted)
+    fn strong_count(&self) -> usize;
+
+    /// Converts this heap string into a [String]. The default implementation copies the contents,
+    /// but types that own a compatible allocation (such as [`Box<str>`]) move it instead.
+    #[inline]
+    fn into_string(self) -> String {
+        String::from(&*self)
+    }
+}
+
+// Copies `parts` into `buffer` returning the number of bytes written. Panics if it is too small.
+#[inline]
+fn copy_parts<'a>(buffer: &mut [u8], parts: impl IntoIterator<Item = &'a str>) -> usize {
+    parts.into_iter().fold(0, |pos, part| {
+        let end = pos + part.len();
+        buffer[pos..end].copy_from_slice(part.as_bytes());
+        end
+    })
+}
+
+macro_rules! impl_ref_counted_heap_storage {
+    ($rc:ident) => {
+        impl HeapStorage for $rc<str> {
+            fn from_parts<'a>(len: usize, parts: impl IntoIterator<Item = &'a str>) -> Self {
+                // An iterator with an exact length is collected with a single allocation
+                let mut bytes: $rc<[u8]> = iter::repeat(0).take(len).collect();
+                let buffer = $rc::get_mut(&mut bytes).expect("Newly created, so must be unique");
+                assert_eq!(
+                    copy_parts(buffer, parts),
+                    len,
+                    "Part lengths must sum to `len`"
+                );
+
+                // SAFETY: Every byte was copied from a valid str, so the buffer is valid UTF-8, and
+                // `str` has the same layout as `[u8]`
+                unsafe { $rc::from_raw($rc::into_raw(bytes) as *const str) }
+            }
+
+            #[inline]
+            fn get_mut(&mut self) -> Option<&mut str> {
+                $rc::get_mut(self)
+            }
+
+            #[inline]
+            fn strong_count(&self) -> usize {
+                $rc::strong_count(self)
+            }
+        }
+    };
+}
+
+impl_ref_counted_heap_storage!(Rc);
+impl_ref_counted_heap_storage!(Arc);
+
+impl HeapStorage for Box<str> {
+    fn from_parts<'a>(len: usize, parts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut buffer = String::with_capacity(len);
+        parts.into_iter().for_each(|part| buffer.push_str(part));
+        assert_eq!(buffer.len(), len, "Part lengths must sum to `len`");
+        // Capacity is exact, so this doesn't reallocate
+        buffer.into_boxed_str()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> Option<&mut str> {
+        Some(self)
+    }
+
+    #[inline]
+    fn strong_count(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn into_string(self) -> String {
+        // Reuses the existing allocation
+        str::into_string(self)
+    }
+}
@@ -0,0 +1,77 @@
+//! Conformance helpers for verifying [FlexStr] heap storage types (requires the `testutil` feature)
+
+use alloc::vec;
+use core::ops::Deref;
+
+use crate::hashed::hash_of;
+use crate::FlexStr;
+
+/// Constructs `sample` as a wrapped literal, a heap string, and (if it fits) an inline string and
+/// asserts that all of them compare equal and hash identically to each other and to the `str`
+/// itself. This is intended as a drop-in conformance test for custom `HEAP` storage types.
+///
+/// # Panics
+/// Panics if any of the invariants do not hold
+/// ```
+/// use std::rc::Rc;
+/// use flexstr::testutil::assert_storage_invariants;
+/// use flexstr::{PTR_SIZED_PAD, STRING_SIZED_INLINE};
+///
+/// type Check = Rc<str>;
+/// assert_storage_invariants::<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Check>("inline");
+/// ```
+pub fn assert_storage_invariants<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>(
+    sample: &'static str,
+) where
+    HEAP: Clone + for<'a> From<&'a str> + Deref<Target = str>,
+{
+    let str_hash = hash_of(sample);
+    let mut variants = vec![
+        FlexStr::<SIZE, PAD1, PAD2, HEAP>::from_static(sample),
+        FlexStr::from_ref_heap(sample),
+    ];
+    if let Ok(inline) = FlexStr::try_inline(sample) {
+        variants.push(inline);
+    }
+
+    for a in &variants {
+        assert_eq!(a, sample, "{:#?} is not equal to its contents", a);
+        assert_eq!(
+            hash_of(a),
+            str_hash,
+            "{:#?} hashes differently than a str",
+            a
+        );
+
+        for b in &variants {
+            assert_eq!(a, b, "{:#?} is not equal to {:#?}", a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+
+    use super::assert_storage_invariants;
+    use crate::{PTR_SIZED_PAD, STRING_SIZED_INLINE};
+
+    const SAMPLES: [&str; 4] = ["", "inline", "☺☺☺", "This is too long to be inlined!!!"];
+
+    #[test]
+    fn builtin_storage_invariants() {
+        for sample in SAMPLES {
+            assert_storage_invariants::<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Rc<str>>(
+                sample,
+            );
+            assert_storage_invariants::<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Arc<str>>(
+                sample,
+            );
+            assert_storage_invariants::<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, Box<str>>(
+                sample,
+            );
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use alloc::string::String;
+use core::any::TypeId;
+use core::mem;
 use core::mem::ManuallyDrop;
 use core::ops::Deref;
 
@@ -130,6 +132,11 @@ where
     /// assert_eq!(a, "TEST");
     /// ```
     fn to_upper(&self) -> FlexStr<SIZE, PAD1, PAD2, HEAP> {
+        // ASCII chars map to exactly one ASCII char, so the simpler ASCII conversion is identical
+        if self.is_ascii() {
+            return self.to_ascii_upper();
+        }
+
         // We estimate capacity based on previous string, but if not ASCII this might be wrong
         let mut buffer = buffer_new!(SIZE);
         let mut builder = builder_new!(buffer, self.len());
@@ -151,6 +158,11 @@ where
     /// assert_eq!(a, "test");
     /// ```
     fn to_lower(&self) -> FlexStr<SIZE, PAD1, PAD2, HEAP> {
+        // ASCII chars map to exactly one ASCII char, so the simpler ASCII conversion is identical
+        if self.is_ascii() {
+            return self.to_ascii_lower();
+        }
+
         // We estimate capacity based on previous string, but if not ASCII this might be wrong
         let mut buffer = buffer_new!(SIZE);
         let mut builder = builder_new!(buffer, self.len());
@@ -202,6 +214,162 @@ where
     }
 }
 
+// *** FindPattern custom trait ***
+
+/// A pattern accepted by the searching, splitting and trimming methods of [FlexStr] (such as
+/// [find](FlexStr::find), [split_once_flex](FlexStr::split_once_flex) and
+/// [trim_matches_flex](FlexStr::trim_matches_flex)). Like the [str] equivalents, this is
+/// implemented for [char], `&str`, `&&str`, `&String`, `&[char]`, `[char; N]`, `&[char; N]` and
+/// `FnMut(char) -> bool` closures, as well as for `&FlexStr`. Patterns are taken by mutable
+/// reference so they can be searched for repeatedly (such as by
+/// [match_indices_flex](FlexStr::match_indices_flex)).
+pub trait FindPattern {
+    /// Returns the byte index of the start of the first match of this pattern in `haystack`
+    fn find_in(&mut self, haystack: &str) -> Option<usize>;
+
+    /// Returns the byte index of the start of the last match of this pattern in `haystack`
+    fn rfind_in(&mut self, haystack: &str) -> Option<usize>;
+
+    /// Returns the number of non-overlapping matches of this pattern in `haystack`
+    fn count_in(&mut self, haystack: &str) -> usize;
+
+    /// Returns true if this pattern matches anywhere in `haystack`
+    fn contained_in(&mut self, haystack: &str) -> bool;
+
+    /// Returns true if this pattern matches at the start of `haystack`
+    fn prefix_of(&mut self, haystack: &str) -> bool;
+
+    /// Returns true if this pattern matches at the end of `haystack`
+    fn suffix_of(&mut self, haystack: &str) -> bool;
+
+    /// Returns the start and end byte indices of the first match of this pattern in `haystack`
+    fn match_in(&mut self, haystack: &str) -> Option<(usize, usize)>;
+
+    /// Returns the start and end byte indices of the last match of this pattern in `haystack`
+    fn rmatch_in(&mut self, haystack: &str) -> Option<(usize, usize)>;
+
+    /// Returns `haystack` with all leading matches of this pattern removed
+    fn trim_start_of<'a>(&mut self, haystack: &'a str) -> &'a str;
+
+    /// Returns `haystack` with all trailing matches of this pattern removed
+    fn trim_end_of<'a>(&mut self, haystack: &'a str) -> &'a str;
+}
+
+// Forwards each `FindPattern` method to the `str` method of the same purpose, where `$pat` is the
+// `str` pattern built from `$this` (a `&mut` to the implementing type)
+macro_rules! impl_find_pattern {
+    ($this:ident => $pat:expr) => {
+        #[inline]
+        fn find_in(&mut self, haystack: &str) -> Option<usize> {
+            let $this = &mut *self;
+            haystack.find($pat)
+        }
+
+        #[inline]
+        fn rfind_in(&mut self, haystack: &str) -> Option<usize> {
+            let $this = &mut *self;
+            haystack.rfind($pat)
+        }
+
+        #[inline]
+        fn count_in(&mut self, haystack: &str) -> usize {
+            let $this = &mut *self;
+            haystack.matches($pat).count()
+        }
+
+        #[inline]
+        fn contained_in(&mut self, haystack: &str) -> bool {
+            let $this = &mut *self;
+            haystack.contains($pat)
+        }
+
+        #[inline]
+        fn prefix_of(&mut self, haystack: &str) -> bool {
+            let $this = &mut *self;
+            haystack.starts_with($pat)
+        }
+
+        #[inline]
+        fn suffix_of(&mut self, haystack: &str) -> bool {
+            let $this = &mut *self;
+            haystack.ends_with($pat)
+        }
+
+        #[inline]
+        fn match_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+            let $this = &mut *self;
+            haystack
+                .match_indices($pat)
+                .next()
+                .map(|(idx, m)| (idx, idx + m.len()))
+        }
+
+        #[inline]
+        fn rmatch_in(&mut self, haystack: &str) -> Option<(usize, usize)> {
+            let $this = &mut *self;
+            haystack
+                .rmatch_indices($pat)
+                .next()
+                .map(|(idx, m)| (idx, idx + m.len()))
+        }
+
+        #[inline]
+        fn trim_start_of<'a>(&mut self, haystack: &'a str) -> &'a str {
+            let $this = &mut *self;
+            haystack.trim_start_matches($pat)
+        }
+
+        #[inline]
+        fn trim_end_of<'a>(&mut self, haystack: &'a str) -> &'a str {
+            let $this = &mut *self;
+            haystack.trim_end_matches($pat)
+        }
+    };
+}
+
+impl FindPattern for char {
+    impl_find_pattern!(ch => *ch);
+}
+
+impl FindPattern for &str {
+    impl_find_pattern!(s => *s);
+}
+
+impl FindPattern for &&str {
+    impl_find_pattern!(s => **s);
+}
+
+impl FindPattern for &String {
+    impl_find_pattern!(s => s.as_str());
+}
+
+impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP> FindPattern
+    for &FlexStr<SIZE, PAD1, PAD2, HEAP>
+where
+    HEAP: Deref<Target = str>,
+{
+    impl_find_pattern!(s => s.as_str());
+}
+
+impl FindPattern for &[char] {
+    impl_find_pattern!(chars => *chars);
+}
+
+impl<const N: usize> FindPattern for [char; N] {
+    impl_find_pattern!(chars => &chars[..]);
+}
+
+impl<const N: usize> FindPattern for &[char; N] {
+    impl_find_pattern!(chars => &chars[..]);
+}
+
+impl<F> FindPattern for F
+where
+    F: FnMut(char) -> bool,
+{
+    impl_find_pattern!(f => f);
+}
+
 // *** Generic `To` trait ***
 
 /// A trait that converts the source to a [FlexStr] without consuming it
@@ -219,8 +387,8 @@ pub trait ToFlex<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP>
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP, HEAP2>
     ToFlex<SIZE, PAD1, PAD2, HEAP> for FlexStr<SIZE, PAD1, PAD2, HEAP2>
 where
-    HEAP: for<'a> From<&'a str>,
-    HEAP2: Clone + Deref<Target = str>,
+    HEAP: for<'a> From<&'a str> + 'static,
+    HEAP2: Clone + Deref<Target = str> + 'static,
 {
     /// ```
     /// use flexstr::{SharedStr, LocalStr, ToFlex};
@@ -361,9 +529,10 @@ pub trait IntoFlex<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP
 impl<const SIZE: usize, const PAD1: usize, const PAD2: usize, HEAP, HEAP2>
     IntoFlex<SIZE, PAD1, PAD2, HEAP> for FlexStr<SIZE, PAD1, PAD2, HEAP2>
 where
-    HEAP: for<'a> From<&'a str>,
-    HEAP2: Deref<Target = str>,
+    HEAP: for<'a> From<&'a str> + 'static,
+    HEAP2: Deref<Target = str> + 'static,
 {
+    /// Converting to the same type is an identity conversion that never reallocates.
     /// ```
     /// use flexstr::{shared_str, SharedStr, LocalStr, IntoFlex};
     ///
@@ -384,9 +553,19 @@ where
     /// let f: SharedStr = e.clone().into_flex();
     /// assert!(f.is_heap());
     /// assert_eq!(e, f);
+    ///
+    /// let g: SharedStr = f.clone().into_flex();
+    /// assert_eq!(f.as_ptr(), g.as_ptr());
     /// ```
     #[inline]
     fn into_flex(self) -> FlexStr<SIZE, PAD1, PAD2, HEAP> {
+        if TypeId::of::<HEAP>() == TypeId::of::<HEAP2>() {
+            let s = ManuallyDrop::new(self);
+            // SAFETY: `HEAP` and `HEAP2` are the same type (and the other parameters are shared), so
+            // this is a move of the same type. `s` is never dropped, so ownership is not duplicated.
+            return unsafe { mem::transmute_copy(&*s) };
+        }
+
         // SAFETY: Marker check is aligned to correct accessed field
         unsafe {
             // TODO: Replace raw union creation with inline calls to special `from_` functions?
@@ -448,7 +627,7 @@ pub trait ToLocalStr {
 
 impl<HEAP> ToLocalStr for FlexStr<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, HEAP>
 where
-    HEAP: Clone + Deref<Target = str>,
+    HEAP: Clone + Deref<Target = str> + 'static,
 {
     /// ```
     /// use flexstr::{SharedStr, ToLocalStr};
@@ -567,14 +746,20 @@ pub trait ToSharedStr {
 
 impl<HEAP> ToSharedStr for FlexStr<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, HEAP>
 where
-    HEAP: Clone + Deref<Target = str>,
+    HEAP: Clone + Deref<Target = str> + 'static,
 {
     /// ```
-    /// use flexstr::{LocalStr, ToSharedStr};
+    /// use flexstr::{LocalStr, SharedStr, ToSharedStr};
     ///
     /// let a: LocalStr = "test".into();
     /// let b = a.to_shared_str();
     /// assert_eq!(a, b);
+    ///
+    /// // Converting from the same type only increments the ref count
+    /// let c = SharedStr::from_ref_heap("test");
+    /// let d = c.to_shared_str();
+    /// assert_eq!(c.as_ptr(), d.as_ptr());
+    /// assert_eq!(c.ref_count(), Some(2));
     /// ```
     #[inline]
     fn to_shared_str(&self) -> SharedStr {
@@ -686,7 +871,7 @@ pub trait IntoLocalStr {
 
 impl<HEAP> IntoLocalStr for FlexStr<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, HEAP>
 where
-    HEAP: Deref<Target = str>,
+    HEAP: Deref<Target = str> + 'static,
 {
     /// ```
     /// use flexstr::{shared_str, SharedStr, IntoFlex, IntoLocalStr};
@@ -732,7 +917,7 @@ pub trait IntoSharedStr {
 
 impl<HEAP> IntoSharedStr for FlexStr<STRING_SIZED_INLINE, PTR_SIZED_PAD, PTR_SIZED_PAD, HEAP>
 where
-    HEAP: Deref<Target = str>,
+    HEAP: Deref<Target = str> + 'static,
 {
     /// ```
     /// use flexstr::{SharedStr, IntoSharedStr, shared_str};
@@ -0,0 +1,109 @@
+use alloc::rc::{self, Rc};
+use alloc::sync::{self, Arc};
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+
+use crate::{LocalStr, SharedStr};
+
+// *** WeakLocalStr ***
+
+/// A weak reference to the heap storage of a [LocalStr] that does not keep it alive. It is created
+/// via [LocalStr::downgrade].
+#[derive(Clone)]
+pub struct WeakLocalStr(rc::Weak<str>);
+
+impl WeakLocalStr {
+    /// Attempts to return a new [LocalStr] sharing the referenced heap storage. Returns `None` if
+    /// all strong references to it have been dropped.
+    /// ```
+    /// use flexstr::LocalStr;
+    ///
+    /// let s = LocalStr::from_ref("This is too long to be inlined!!!");
+    /// let weak = s.downgrade().unwrap();
+    /// assert_eq!(weak.upgrade().unwrap(), s);
+    ///
+    /// drop(s);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn upgrade(&self) -> Option<LocalStr> {
+        self.0.upgrade().map(LocalStr::from_heap)
+    }
+}
+
+impl Debug for WeakLocalStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("(WeakLocalStr)")
+    }
+}
+
+impl LocalStr {
+    /// Creates a [WeakLocalStr] referencing the heap storage of this string. Returns `None` if this
+    /// is not a heap based string, as static and inline strings have nothing to reference count.
+    /// ```
+    /// use flexstr::{local_str, LocalStr};
+    ///
+    /// assert!(local_str!("static").downgrade().is_none());
+    /// assert!(LocalStr::from_ref("inline").downgrade().is_none());
+    /// assert!(LocalStr::from_ref_heap("heap").downgrade().is_some());
+    /// ```
+    #[inline]
+    pub fn downgrade(&self) -> Option<WeakLocalStr> {
+        self.as_heap().map(|heap| WeakLocalStr(Rc::downgrade(heap)))
+    }
+}
+
+// *** WeakSharedStr ***
+
+/// A weak reference to the heap storage of a [SharedStr] that does not keep it alive. It is created
+/// via [SharedStr::downgrade] and is useful for caches where entries should be evicted once no
+/// strong references remain.
+#[derive(Clone)]
+pub struct WeakSharedStr(sync::Weak<str>);
+
+impl WeakSharedStr {
+    /// Attempts to return a new [SharedStr] sharing the referenced heap storage. Returns `None` if
+    /// all strong references to it have been dropped.
+    /// ```
+    /// use flexstr::SharedStr;
+    ///
+    /// let s = SharedStr::from_ref("This is too long to be inlined!!!");
+    /// let s2 = s.clone();
+    /// let weak = s.downgrade().unwrap();
+    /// assert_eq!(weak.upgrade().unwrap(), s);
+    ///
+    /// drop(s);
+    /// assert!(weak.upgrade().is_some());
+    /// drop(s2);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn upgrade(&self) -> Option<SharedStr> {
+        self.0.upgrade().map(SharedStr::from_heap)
+    }
+}
+
+impl Debug for WeakSharedStr {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("(WeakSharedStr)")
+    }
+}
+
+impl SharedStr {
+    /// Creates a [WeakSharedStr] referencing the heap storage of this string. Returns `None` if this
+    /// is not a heap based string, as static and inline strings have nothing to reference count.
+    /// ```
+    /// use flexstr::{shared_str, SharedStr};
+    ///
+    /// assert!(shared_str!("static").downgrade().is_none());
+    /// assert!(SharedStr::from_ref("inline").downgrade().is_none());
+    /// assert!(SharedStr::from_ref_heap("heap").downgrade().is_some());
+    /// ```
+    #[inline]
+    pub fn downgrade(&self) -> Option<WeakSharedStr> {
+        self.as_heap()
+            .map(|heap| WeakSharedStr(Arc::downgrade(heap)))
+    }
+}